@@ -0,0 +1,280 @@
+//! Compares the bitboard `Board` with the byte-array board it replaced.
+//!
+//! Run with `cargo bench -p tetroku-lib`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use tetroku_lib::{Board, Coordinate, Mino, Position, Rng};
+
+const SIZE: Coordinate = 9;
+
+/// The previous implementation, which stored each cell as a bit in a byte array and checked minos square by square.
+#[derive(Clone, Copy)]
+struct OldBoard {
+	squares: [u8; 11],
+}
+
+impl OldBoard {
+	fn index(x: Coordinate, y: Coordinate) -> Option<usize> {
+		((0..SIZE).contains(&x) && (0..SIZE).contains(&y))
+			.then(|| usize::try_from(y * SIZE + x).unwrap())
+	}
+
+	fn occupied(&self, index: usize) -> bool {
+		self.squares[index / 8] & (1 << (index % 8)) > 0
+	}
+
+	fn set(&mut self, index: usize, value: bool) {
+		let byte = &mut self.squares[index / 8];
+		*byte = *byte & !(1 << (index % 8)) | (u8::from(value) << (index % 8));
+	}
+
+	fn place_at(&mut self, mino: Mino, (x, y): (Coordinate, Coordinate)) -> bool {
+		let mut atomic = *self;
+		for (dx, dy) in mino.iter() {
+			match Self::index(x + dx, y + dy) {
+				Some(index) if !self.occupied(index) => atomic.set(index, true),
+				_ => return false,
+			}
+		}
+		*self = atomic;
+		true
+	}
+
+	fn can_place_anywhere(&self, mino: Mino) -> bool {
+		let (min, max) = (mino.min_point(), mino.max_point());
+		(-min.1..SIZE - max.1).any(|y| {
+			(-min.0..SIZE - max.0).any(|x| {
+				mino
+					.iter()
+					.all(|(dx, dy)| Self::index(x + dx, y + dy).is_some_and(|index| !self.occupied(index)))
+			})
+		})
+	}
+
+	fn count_placements(&self, mino: Mino) -> usize {
+		let (min, max) = (mino.min_point(), mino.max_point());
+		(-min.1..SIZE - max.1)
+			.flat_map(|y| (-min.0..SIZE - max.0).map(move |x| (x, y)))
+			.filter(|&(x, y)| {
+				mino
+					.iter()
+					.all(|(dx, dy)| Self::index(x + dx, y + dy).is_some_and(|index| !self.occupied(index)))
+			})
+			.count()
+	}
+
+	/// The cells of row `y`. Rows, columns and boxes are fixed arrays, as they were before, so that nothing is allocated.
+	fn row(y: Coordinate) -> [(Coordinate, Coordinate); 9] {
+		std::array::from_fn(|x| (Coordinate::try_from(x).unwrap(), y))
+	}
+
+	fn column(x: Coordinate) -> [(Coordinate, Coordinate); 9] {
+		std::array::from_fn(|y| (x, Coordinate::try_from(y).unwrap()))
+	}
+
+	fn square(i: Coordinate) -> [(Coordinate, Coordinate); 9] {
+		std::array::from_fn(|j| {
+			let j = Coordinate::try_from(j).unwrap();
+			(i % 3 * 3 + j % 3, i / 3 * 3 + j / 3)
+		})
+	}
+
+	fn remove_filled(&mut self) -> usize {
+		let board = *self;
+		let filled = (0..SIZE)
+			.map(Self::row)
+			.chain((0..SIZE).map(Self::column))
+			.chain((0..SIZE).map(Self::square))
+			.filter(|cells| {
+				cells
+					.iter()
+					.all(|&(x, y)| board.occupied(Self::index(x, y).unwrap()))
+			});
+
+		let mut count = 0;
+		for cells in filled {
+			count += 1;
+			for (x, y) in cells {
+				self.set(Self::index(x, y).unwrap(), false);
+			}
+		}
+		count
+	}
+}
+
+/// Random boards in both representations, about a third full.
+fn boards() -> Vec<(Board, OldBoard)> {
+	let mut rng = Rng::new(0);
+	(0..100)
+		.map(|_| {
+			let mut board = Board::new();
+			let mut old = OldBoard { squares: [0; 11] };
+			for y in 0..SIZE {
+				for x in 0..SIZE {
+					let occupied = rng.below(3) == 0;
+					board.set(Position::new(x, y).unwrap(), occupied);
+					old.set(OldBoard::index(x, y).unwrap(), occupied);
+				}
+			}
+			(board, old)
+		})
+		.collect()
+}
+
+fn bench(name: &str, mut run: impl FnMut() -> usize) -> Duration {
+	// Warm up, and make sure that the work isn't optimized away.
+	black_box(run());
+
+	let iterations = 20;
+	let start = Instant::now();
+	for _ in 0..iterations {
+		black_box(run());
+	}
+	let per_iteration = start.elapsed() / iterations;
+	println!("{name:>32}: {per_iteration:>12.2?}");
+	per_iteration
+}
+
+fn compare(name: &str, new: impl FnMut() -> usize, old: impl FnMut() -> usize) {
+	println!("{name}:");
+	let new = bench("bitboard", new);
+	let old = bench("byte array", old);
+	println!(
+		"{:>32}: {:>11.1}x",
+		"speedup",
+		old.as_secs_f64() / new.as_secs_f64()
+	);
+}
+
+fn main() {
+	let boards = boards();
+	let minos: Vec<Mino> = Mino::all()
+		.flat_map(|mino| [mino, mino.rotate_cw_90()])
+		.collect();
+	let positions: Vec<(Coordinate, Coordinate)> = (-2..SIZE)
+		.flat_map(|y| (-2..SIZE).map(move |x| (x, y)))
+		.collect();
+
+	compare(
+		"can_place_anywhere",
+		|| {
+			boards
+				.iter()
+				.flat_map(|(board, _)| minos.iter().filter(|&&mino| board.can_place_anywhere(mino)))
+				.count()
+		},
+		|| {
+			boards
+				.iter()
+				.flat_map(|(_, old)| minos.iter().filter(|&&mino| old.can_place_anywhere(mino)))
+				.count()
+		},
+	);
+
+	compare(
+		"count_legal_placements",
+		|| {
+			boards
+				.iter()
+				.flat_map(|(board, _)| minos.iter().map(|&mino| board.count_legal_placements(mino)))
+				.sum()
+		},
+		|| {
+			boards
+				.iter()
+				.flat_map(|(_, old)| minos.iter().map(|&mino| old.count_placements(mino)))
+				.sum()
+		},
+	);
+
+	compare(
+		"place_at",
+		|| {
+			let mut placed = 0;
+			for (board, _) in &boards {
+				for &mino in &minos {
+					for &position in &positions {
+						placed += usize::from({ *board }.place_at(mino, position).is_ok());
+					}
+				}
+			}
+			placed
+		},
+		|| {
+			let mut placed = 0;
+			for (_, old) in &boards {
+				for &mino in &minos {
+					for &position in &positions {
+						placed += usize::from({ *old }.place_at(mino, position));
+					}
+				}
+			}
+			placed
+		},
+	);
+
+	compare(
+		"place_at and remove_filled",
+		|| {
+			let mut removed = 0;
+			for (board, _) in &boards {
+				for &mino in &minos {
+					for &position in &positions {
+						let mut board = *board;
+						if board.place_at(mino, position).is_ok() {
+							removed += board.remove_filled();
+						}
+					}
+				}
+			}
+			removed
+		},
+		|| {
+			let mut removed = 0;
+			for (_, old) in &boards {
+				for &mino in &minos {
+					for &position in &positions {
+						let mut old = *old;
+						if old.place_at(mino, position) {
+							removed += old.remove_filled();
+						}
+					}
+				}
+			}
+			removed
+		},
+	);
+
+	compare(
+		"place_and_clear",
+		|| {
+			let mut removed = 0;
+			for (board, _) in &boards {
+				for &mino in &minos {
+					for &position in &positions {
+						if let Ok((_, cleared)) = board.place_and_clear(mino, position) {
+							removed += cleared.len();
+						}
+					}
+				}
+			}
+			removed
+		},
+		|| {
+			let mut removed = 0;
+			for (_, old) in &boards {
+				for &mino in &minos {
+					for &position in &positions {
+						let mut old = *old;
+						if old.place_at(mino, position) {
+							removed += old.remove_filled();
+						}
+					}
+				}
+			}
+			removed
+		},
+	);
+}
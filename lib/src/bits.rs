@@ -0,0 +1,147 @@
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not, Shl};
+
+use crate::layout::MAX_CELLS;
+
+const WORD_BITS: usize = u128::BITS as usize;
+const WORDS: usize = MAX_CELLS / WORD_BITS;
+
+/// A set of up to `MAX_CELLS` cells, indexed as by `Layout::cell_index`.
+///
+/// Boards of up to 128 cells, including the standard 9x9 board, only ever use the low word.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct Bits([u128; WORDS]);
+
+impl Bits {
+	pub(crate) const ZERO: Self = Self([0; WORDS]);
+
+	/// `self` with `index` added, for use in const contexts.
+	#[inline]
+	#[must_use]
+	pub(crate) const fn with(mut self, index: usize) -> Self {
+		self.0[index / WORD_BITS] |= 1 << (index % WORD_BITS);
+		self
+	}
+
+	/// The set whose low word is `word`.
+	#[inline]
+	#[must_use]
+	pub(crate) fn from_low(word: u128) -> Self {
+		let mut ret = Self::ZERO;
+		ret.0[0] = word;
+		ret
+	}
+
+	#[inline]
+	#[must_use]
+	pub(crate) fn get(self, index: usize) -> bool {
+		self.0[index / WORD_BITS] & (1 << (index % WORD_BITS)) != 0
+	}
+
+	#[inline]
+	pub(crate) fn set(&mut self, index: usize, value: bool) {
+		let word = &mut self.0[index / WORD_BITS];
+		let bit = index % WORD_BITS;
+		*word = *word & !(1 << bit) | (u128::from(value) << bit);
+	}
+
+	#[inline]
+	#[must_use]
+	pub(crate) fn is_zero(self) -> bool {
+		self == Self::ZERO
+	}
+
+	/// Whether every cell in `other` is also in `self`.
+	#[inline]
+	#[must_use]
+	pub(crate) fn contains(self, other: Self) -> bool {
+		other & !self == Self::ZERO
+	}
+}
+
+impl BitAnd for Bits {
+	type Output = Self;
+
+	#[inline]
+	fn bitand(mut self, rhs: Self) -> Self {
+		self &= rhs;
+		self
+	}
+}
+
+impl BitAndAssign for Bits {
+	#[inline]
+	fn bitand_assign(&mut self, rhs: Self) {
+		for (word, rhs) in self.0.iter_mut().zip(rhs.0) {
+			*word &= rhs;
+		}
+	}
+}
+
+impl BitOr for Bits {
+	type Output = Self;
+
+	#[inline]
+	fn bitor(mut self, rhs: Self) -> Self {
+		self |= rhs;
+		self
+	}
+}
+
+impl BitOrAssign for Bits {
+	#[inline]
+	fn bitor_assign(&mut self, rhs: Self) {
+		for (word, rhs) in self.0.iter_mut().zip(rhs.0) {
+			*word |= rhs;
+		}
+	}
+}
+
+impl Not for Bits {
+	type Output = Self;
+
+	#[inline]
+	fn not(self) -> Self {
+		Self(self.0.map(|word| !word))
+	}
+}
+
+/// Bits shifted past `MAX_CELLS` are discarded.
+impl Shl<usize> for Bits {
+	type Output = Self;
+
+	#[inline]
+	fn shl(self, amount: usize) -> Self {
+		let mut ret = Self::ZERO;
+		let words = amount / WORD_BITS;
+		let bits = amount % WORD_BITS;
+		for index in words..WORDS {
+			let source = index - words;
+			ret.0[index] = self.0[source] << bits;
+			if bits > 0 && source > 0 {
+				ret.0[index] |= self.0[source - 1] >> (WORD_BITS - bits);
+			}
+		}
+		ret
+	}
+}
+
+#[test]
+fn test_bits() {
+	let mut bits = Bits::ZERO;
+	bits.set(3, true);
+	bits.set(200, true);
+	assert!(bits.get(3) && bits.get(200) && !bits.get(4));
+	assert_eq!(bits, Bits::ZERO.with(200).with(3));
+
+	let low = Bits::from_low(0b11 << 126);
+	assert_eq!(low << 1, Bits::ZERO.with(127).with(128));
+	assert_eq!(Bits::ZERO.with(100) << 200, Bits::ZERO);
+	assert_eq!(
+		!Bits::ZERO << (MAX_CELLS - 1),
+		Bits::ZERO.with(MAX_CELLS - 1)
+	);
+
+	assert!((!Bits::ZERO).contains(bits));
+	assert!(bits.contains(Bits::ZERO.with(3)));
+	assert!(!Bits::from_low(u128::MAX).contains(bits));
+}
@@ -1,23 +1,31 @@
-use std::fmt::{self, Debug, Formatter};
+use std::fmt::{self, Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
 
-use crate::mino::Mino;
-use crate::util::{grid_fmt, min_bytes_for_bits, Coordinate};
-
-/// The length of an edge.
-pub const BOARD_SIZE: i8 = 9;
-const NUM_SQUARES: i8 = BOARD_SIZE * BOARD_SIZE;
+use crate::bits::Bits;
+use crate::layout::{Layout, MAX_BOARD_SIZE};
+use crate::mino::{Mino, MAX_MINO_SIZE};
+use crate::util::{grid_fmt, Coordinate};
 
+/// With the `serde` feature, positions are serialized as `{ "x": x, "y": y }`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(into = "PositionRepr", from = "PositionRepr")
+)]
 pub struct Position {
+	/// Row-major with a stride of `MAX_BOARD_SIZE`, so that positions don't depend on the layout.
 	index: u8,
 }
 
 impl Position {
-	/// Returns `None` if `x` or `y` are outside of the board.
+	/// Returns `None` if `x` or `y` are outside of the largest possible board.
+	/// Use `Layout::position` to check against a particular board.
 	#[inline]
 	#[must_use]
 	pub fn new(x: Coordinate, y: Coordinate) -> Option<Self> {
-		if !(0..BOARD_SIZE).contains(&x) || !(0..BOARD_SIZE).contains(&y) {
+		if !(0..MAX_BOARD_SIZE).contains(&x) || !(0..MAX_BOARD_SIZE).contains(&y) {
 			None
 		} else {
 			Some(Self::new_unchecked(x, y))
@@ -29,15 +37,17 @@ impl Position {
 	#[must_use]
 	#[allow(clippy::missing_panics_doc)]
 	pub fn new_unchecked(x: Coordinate, y: Coordinate) -> Self {
+		let x = u8::try_from(x).unwrap();
+		let y = u8::try_from(y).unwrap();
 		Self {
-			index: (y * BOARD_SIZE + x).try_into().unwrap(),
+			index: y * MAX_BOARD_SIZE as u8 + x,
 		}
 	}
 
 	#[inline]
 	#[must_use]
 	pub fn x(self) -> Coordinate {
-		(self.index % BOARD_SIZE as u8)
+		(self.index % MAX_BOARD_SIZE as u8)
 			.try_into()
 			.unwrap_or_else(|_| unreachable!())
 	}
@@ -45,7 +55,7 @@ impl Position {
 	#[inline]
 	#[must_use]
 	pub fn y(self) -> Coordinate {
-		(self.index / BOARD_SIZE as u8)
+		(self.index / MAX_BOARD_SIZE as u8)
 			.try_into()
 			.unwrap_or_else(|_| unreachable!())
 	}
@@ -55,54 +65,160 @@ impl Position {
 	pub fn to_xy(self) -> (Coordinate, Coordinate) {
 		(self.x(), self.y())
 	}
+}
 
-	#[inline]
-	#[must_use]
-	pub fn all() -> impl ExactSizeIterator<Item = Self> {
-		(0..NUM_SQUARES).map(|index| Position {
-			index: index.try_into().unwrap_or_else(|_| unreachable!()),
-		})
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct PositionRepr {
+	#[serde(deserialize_with = "deserialize_coordinate")]
+	x: Coordinate,
+	#[serde(deserialize_with = "deserialize_coordinate")]
+	y: Coordinate,
+}
+
+#[cfg(feature = "serde")]
+impl From<Position> for PositionRepr {
+	fn from(position: Position) -> Self {
+		Self {
+			x: position.x(),
+			y: position.y(),
+		}
+	}
+}
+
+#[cfg(feature = "serde")]
+impl From<PositionRepr> for Position {
+	fn from(PositionRepr { x, y }: PositionRepr) -> Self {
+		Self::new_unchecked(x, y)
+	}
+}
+
+/// Accepts coordinates that are within the largest possible board.
+#[cfg(feature = "serde")]
+fn deserialize_coordinate<'de, D: serde::Deserializer<'de>>(
+	deserializer: D,
+) -> Result<Coordinate, D::Error> {
+	let coordinate = <Coordinate as serde::Deserialize>::deserialize(deserializer)?;
+	if (0..MAX_BOARD_SIZE).contains(&coordinate) {
+		Ok(coordinate)
+	} else {
+		Err(serde::de::Error::custom(format_args!(
+			"coordinate {coordinate} is out of range"
+		)))
 	}
 }
 
-#[derive(Default, Clone, Copy)]
+/// With the `serde` feature, boards are serialized as `{ "layout": layout, "rows": ["#..", ...] }`,
+/// with the rows in the format of `Display`. The layout is omitted if it is the standard layout.
+#[derive(Clone, Copy, Eq)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(into = "BoardRepr", try_from = "BoardRepr")
+)]
 pub struct Board {
-	squares: [u8; min_bytes_for_bits(NUM_SQUARES as usize)],
+	layout: &'static Layout,
+	/// The occupied cells.
+	cells: Bits,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct BoardRepr {
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	layout: Option<Layout>,
+	rows: Vec<String>,
+}
+
+#[cfg(feature = "serde")]
+impl From<Board> for BoardRepr {
+	fn from(board: Board) -> Self {
+		Self {
+			layout: (board.layout != Layout::standard()).then(|| board.layout.clone()),
+			rows: board.to_string().lines().map(str::to_owned).collect(),
+		}
+	}
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<BoardRepr> for Board {
+	type Error = BoardParseError;
+
+	fn try_from(repr: BoardRepr) -> Result<Self, Self::Error> {
+		let layout = repr.layout.map_or_else(Layout::standard, Layout::intern);
+		Self::parse(&repr.rows.join("\n"), layout)
+	}
+}
+
+impl Default for Board {
+	fn default() -> Self {
+		Self::new()
+	}
 }
 
 impl Board {
+	/// An empty board with the standard layout.
 	#[inline]
 	#[must_use]
 	pub fn new() -> Self {
+		Self::with_layout(Layout::standard())
+	}
+
+	/// An empty board with the given layout.
+	#[inline]
+	#[must_use]
+	pub fn with_layout(layout: &'static Layout) -> Self {
 		Self {
-			squares: Default::default(),
+			layout,
+			cells: Bits::ZERO,
 		}
 	}
 
+	#[inline]
+	#[must_use]
+	pub fn layout(&self) -> &'static Layout {
+		self.layout
+	}
+
+	/// # Panics
+	///
+	/// Panics if `position` is outside of the board.
 	#[inline]
 	#[must_use]
 	pub fn occupied(&self, position: Position) -> bool {
-		let byte = position.index / 8;
-		let bit = position.index % 8;
-		(self.squares[usize::from(byte)] & (1 << bit)) > 0
+		self.cells.get(self.layout.cell_index(position))
 	}
 
+	/// # Panics
+	///
+	/// Panics if `position` is outside of the board.
 	pub fn set(&mut self, position: Position, value: bool) {
-		let byte = usize::from(position.index / 8);
-		let bit = position.index % 8;
-		self.squares[byte] = self.squares[byte] & !(1 << bit) | (u8::from(value) << bit);
+		self.cells.set(self.layout.cell_index(position), value);
 	}
 
 	#[inline]
 	#[must_use]
 	pub fn is_empty(&self) -> bool {
-		self.squares.iter().all(|&square| square == 0)
+		self.cells.is_zero()
 	}
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct SquareIndex {
-	index: u8,
+/// Whether `a` and `b` are the same layout, which is cheap if they're both interned.
+fn same_layout(a: &'static Layout, b: &'static Layout) -> bool {
+	std::ptr::eq(a, b) || a == b
+}
+
+impl PartialEq for Board {
+	fn eq(&self, other: &Self) -> bool {
+		self.cells == other.cells && same_layout(self.layout, other.layout)
+	}
+}
+
+/// Only the cells are hashed, so that hashing doesn't go through the whole layout.
+impl Hash for Board {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.cells.hash(state);
+	}
 }
 
 impl Debug for Board {
@@ -110,8 +226,16 @@ impl Debug for Board {
 		grid_fmt(
 			formatter,
 			"Board",
-			BOARD_SIZE as _,
-			BOARD_SIZE as _,
+			self
+				.layout
+				.height()
+				.try_into()
+				.unwrap_or_else(|_| unreachable!()),
+			self
+				.layout
+				.width()
+				.try_into()
+				.unwrap_or_else(|_| unreachable!()),
 			|x, y| {
 				if self.occupied(Position::new_unchecked(
 					x.try_into().unwrap_or_else(|_| unreachable!()),
@@ -126,68 +250,220 @@ impl Debug for Board {
 	}
 }
 
-// If this causes an error, all the code in the following impl block needs to be changed.
-#[allow(clippy::assertions_on_constants)]
-const _: () = {
-	assert!(BOARD_SIZE == 9);
-};
+/// One line per row, with `#` for occupied squares and `.` for empty ones.
+///
+/// See also `Board::one_line`.
+impl Display for Board {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		self.write_rows(formatter, "\n")
+	}
+}
 
-impl SquareIndex {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoardParseError {
+	/// The number of rows doesn't match the layout.
+	WrongHeight { expected: usize, found: usize },
+	/// A row has a different length than the layout's width. Rows are numbered from 1.
+	WrongWidth {
+		row: usize,
+		expected: usize,
+		found: usize,
+	},
+	/// A character other than `#` or `.` was found. Rows and columns are numbered from 1.
+	InvalidChar {
+		row: usize,
+		column: usize,
+		char: char,
+	},
+}
+
+impl Display for BoardParseError {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::WrongHeight { expected, found } => {
+				write!(formatter, "expected {expected} rows, found {found}")
+			}
+			Self::WrongWidth {
+				row,
+				expected,
+				found,
+			} => write!(
+				formatter,
+				"expected {expected} squares in row {row}, found {found}"
+			),
+			Self::InvalidChar { row, column, char } => write!(
+				formatter,
+				"invalid character {char:?} at row {row}, column {column}; expected `#` or `.`"
+			),
+		}
+	}
+}
+
+impl std::error::Error for BoardParseError {}
+
+/// Parses a board with the standard layout. Use `Board::parse` for other layouts.
+impl FromStr for Board {
+	type Err = BoardParseError;
+
+	fn from_str(text: &str) -> Result<Self, Self::Err> {
+		Self::parse(text, Layout::standard())
+	}
+}
+
+impl Board {
+	fn write_rows(&self, formatter: &mut Formatter<'_>, separator: &str) -> fmt::Result {
+		for y in 0..self.layout.height() {
+			if y > 0 {
+				formatter.write_str(separator)?;
+			}
+			for x in 0..self.layout.width() {
+				let occupied = self.occupied(Position::new_unchecked(x, y));
+				formatter.write_str(if occupied { "#" } else { "." })?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Formats the board on a single line, with rows separated by `/`, for example for logs.
+	///
+	/// `Board::parse` accepts this format too.
 	#[must_use]
-	pub fn top_left(self) -> Position {
-		let squares_per_row = 3;
-		let horizontal_between_squares = 3;
-		let vertical_between_squares = 27;
+	pub fn one_line(&self) -> impl Display + '_ {
+		struct OneLine<'a>(&'a Board);
+
+		impl Display for OneLine<'_> {
+			fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+				self.0.write_rows(formatter, "/")
+			}
+		}
 
-		let i_y = self.index / squares_per_row * vertical_between_squares;
-		let i_x = self.index % squares_per_row * horizontal_between_squares;
-		Position { index: i_y + i_x }
+		OneLine(self)
 	}
 
+	/// Parse a board with the given layout, in the format of either `Display` or `Board::one_line`.
+	///
+	/// Leading and trailing whitespace on each row is ignored, as are blank lines.
+	///
+	/// # Errors
+	///
+	/// Fails if the dimensions don't match the layout or there is an invalid character.
+	pub fn parse(text: &str, layout: &'static Layout) -> Result<Self, BoardParseError> {
+		let rows: Vec<&str> = text
+			.split(['\n', '/'])
+			.map(str::trim)
+			.filter(|row| !row.is_empty())
+			.collect();
+		let usize = |value: Coordinate| usize::try_from(value).unwrap_or_else(|_| unreachable!());
+		if rows.len() != usize(layout.height()) {
+			return Err(BoardParseError::WrongHeight {
+				expected: usize(layout.height()),
+				found: rows.len(),
+			});
+		}
+
+		let mut ret = Self::with_layout(layout);
+		for (y, row) in (0..layout.height()).zip(&rows) {
+			let row_number = usize(y) + 1;
+			let found = row.chars().count();
+			if found != usize(layout.width()) {
+				return Err(BoardParseError::WrongWidth {
+					row: row_number,
+					expected: usize(layout.width()),
+					found,
+				});
+			}
+			for (x, char) in (0..layout.width()).zip(row.chars()) {
+				let occupied = match char {
+					'#' => true,
+					'.' => false,
+					_ => {
+						return Err(BoardParseError::InvalidChar {
+							row: row_number,
+							column: usize(x) + 1,
+							char,
+						})
+					}
+				};
+				ret.set(Position::new_unchecked(x, y), occupied);
+			}
+		}
+		Ok(ret)
+	}
+}
+
+/// Identifies one of the squares (boxes) of a `Layout`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(transparent)
+)]
+pub struct SquareIndex {
+	index: u8,
+}
+
+impl SquareIndex {
 	#[inline]
 	#[must_use]
-	pub fn all_within(self) -> impl ExactSizeIterator<Item = Position> + Clone {
-		let top_left = self.top_left().index;
-		[0, 1, 2, 9, 10, 11, 18, 19, 20]
-			.iter()
-			.map(move |&offset| Position {
-				index: top_left + offset,
-			})
+	pub(crate) fn new(index: u8) -> Self {
+		Self { index }
 	}
 
+	/// Squares of boxed layouts are numbered in row-major order.
 	#[inline]
 	#[must_use]
-	pub fn all() -> impl ExactSizeIterator<Item = Self> + Clone {
-		(0..9).map(|index| SquareIndex { index })
+	pub fn get(self) -> usize {
+		self.index.into()
+	}
+
+	/// All positions within this square on the given layout.
+	#[inline]
+	pub fn all_within(self, layout: &Layout) -> impl Iterator<Item = Position> + Clone + '_ {
+		layout.square(self)
 	}
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(rename_all = "snake_case")
+)]
 pub enum WouldRemove {
 	Horizontal {
+		#[cfg_attr(feature = "serde", serde(deserialize_with = "deserialize_coordinate"))]
 		y: Coordinate,
 	},
 	Vertical {
+		#[cfg_attr(feature = "serde", serde(deserialize_with = "deserialize_coordinate"))]
 		x: Coordinate,
 	},
 	Square {
-		/// Row-major.
 		index: SquareIndex,
 	},
 }
 
 impl WouldRemove {
-	pub fn iter(self) -> impl Iterator<Item = Position> + Clone {
-		fn arr(mut iter: impl Iterator<Item = Position>) -> [Position; 9] {
-			std::array::from_fn(|_| iter.next().unwrap_or_else(|| unreachable!()))
-		}
-
-		match self {
-			Self::Horizontal { y } => arr((0..BOARD_SIZE).map(|x| Position::new_unchecked(x, y))),
-			Self::Vertical { x } => arr((0..BOARD_SIZE).map(|y| Position::new_unchecked(x, y))),
-			Self::Square { index } => arr(index.all_within()),
-		}
-		.into_iter()
+	/// All positions in the feature on the given layout.
+	pub fn iter(self, layout: &Layout) -> impl Iterator<Item = Position> + Clone + '_ {
+		let (horizontal, vertical, square) = match self {
+			Self::Horizontal { y } => (
+				Some((0..layout.width()).map(move |x| Position::new_unchecked(x, y))),
+				None,
+				None,
+			),
+			Self::Vertical { x } => (
+				None,
+				Some((0..layout.height()).map(move |y| Position::new_unchecked(x, y))),
+				None,
+			),
+			Self::Square { index } => (None, None, Some(index.all_within(layout))),
+		};
+		horizontal
+			.into_iter()
+			.flatten()
+			.chain(vertical.into_iter().flatten())
+			.chain(square.into_iter().flatten())
 	}
 }
 
@@ -200,6 +476,17 @@ pub enum PlaceError {
 	Conflicts,
 }
 
+impl Display for PlaceError {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::OutOfBounds => formatter.write_str("the mino would stick out of the board"),
+			Self::Conflicts => formatter.write_str("the mino would overlap occupied squares"),
+		}
+	}
+}
+
+impl std::error::Error for PlaceError {}
+
 impl From<OutOfBounds> for PlaceError {
 	fn from(OutOfBounds: OutOfBounds) -> Self {
 		Self::OutOfBounds
@@ -207,73 +494,124 @@ impl From<OutOfBounds> for PlaceError {
 }
 
 impl Board {
-	fn map_mino_coordinates(
+	/// The cells covered by `mino` at `mino_position`.
+	fn mino_mask(
+		&self,
 		mino: Mino,
 		mino_position: (Coordinate, Coordinate),
-	) -> impl Iterator<Item = Result<Position, OutOfBounds>> {
-		mino
-			.iter()
-			.map(move |pos_in_mino| {
-				(
-					mino_position.0 + pos_in_mino.0,
-					mino_position.1 + pos_in_mino.1,
-				)
-			})
-			.map(|(x, y)| Position::new(x, y).ok_or(OutOfBounds))
+	) -> Result<Bits, OutOfBounds> {
+		let usize = |value: Coordinate| usize::try_from(value).unwrap_or_else(|_| unreachable!());
+		let (x, y) = mino_position;
+		// Any further and the mino would certainly be out of bounds, and the shifts below could overflow.
+		if x <= -MAX_MINO_SIZE || x >= MAX_BOARD_SIZE {
+			return Err(OutOfBounds);
+		}
+
+		let width = usize(self.layout.width());
+		let row_mask = (1_u32 << width) - 1;
+		let mut ret = Bits::ZERO;
+		for y_in_mino in 0..mino.height() {
+			let row = u32::from(mino.row(y_in_mino));
+			if row == 0 {
+				continue;
+			}
+			let board_y = y.checked_add(y_in_mino).ok_or(OutOfBounds)?;
+			if !(0..self.layout.height()).contains(&board_y) {
+				return Err(OutOfBounds);
+			}
+			let row = if x >= 0 {
+				row << x
+			} else {
+				if row & ((1 << -x) - 1) != 0 {
+					return Err(OutOfBounds);
+				}
+				row >> -x
+			};
+			if row & !row_mask != 0 {
+				return Err(OutOfBounds);
+			}
+			ret |= Bits::from_low(row.into()) << (usize(board_y) * width);
+		}
+		Ok(ret)
 	}
 
 	#[inline]
 	#[must_use]
-	pub fn is_in_bounds(mino: Mino, mino_position: (Coordinate, Coordinate)) -> bool {
-		Self::map_mino_coordinates(mino, mino_position).all(|res| res.is_ok())
+	pub fn is_in_bounds(&self, mino: Mino, mino_position: (Coordinate, Coordinate)) -> bool {
+		self.mino_mask(mino, mino_position).is_ok()
 	}
 
-	fn iter_conflicts(
+	/// All positions where `mino` can be placed without conflicts, in row-major order.
+	///
+	/// These are the positions for which `place_at` would succeed.
+	pub fn legal_placements(
 		&self,
 		mino: Mino,
-		mino_position: (Coordinate, Coordinate),
-	) -> impl Iterator<Item = Result<Position, OutOfBounds>> + '_ {
-		Self::map_mino_coordinates(mino, mino_position)
-			.filter(|res| res.map_or(true, |position| self.occupied(position)))
-	}
-
-	#[must_use]
-	pub fn can_place_anywhere(&self, mino: Mino) -> bool {
+	) -> impl Iterator<Item = (Coordinate, Coordinate)> + Clone + '_ {
 		let min = mino.min_point();
 		let max = mino.max_point();
 
 		let min_place = (-min.0, -min.1);
-		let max_place = (BOARD_SIZE - 1 - max.0, BOARD_SIZE - 1 - max.1);
+		let max_place = (
+			self.layout.width() - 1 - max.0,
+			self.layout.height() - 1 - max.1,
+		);
+
+		// Every placement within the range is the mask at `min_place` shifted without wrapping.
+		let base = self.mino_mask(mino, min_place).ok();
+		let width = usize::try_from(self.layout.width()).unwrap_or_else(|_| unreachable!());
+		let offset = move |position: (Coordinate, Coordinate)| {
+			usize::try_from(position.1 - min_place.1).unwrap_or_else(|_| unreachable!()) * width
+				+ usize::try_from(position.0 - min_place.0).unwrap_or_else(|_| unreachable!())
+		};
 
 		(min_place.1..=max_place.1)
-			.flat_map(|y| (min_place.0..=max_place.0).map(move |x| (x, y)))
-			.any(|position| self.iter_conflicts(mino, position).next().is_none())
+			.flat_map(move |y| (min_place.0..=max_place.0).map(move |x| (x, y)))
+			.filter(move |&position| {
+				base.is_some_and(|base| (self.cells & (base << offset(position))).is_zero())
+			})
+	}
+
+	#[must_use]
+	pub fn can_place_anywhere(&self, mino: Mino) -> bool {
+		self.legal_placements(mino).next().is_some()
+	}
+
+	/// The number of positions where `mino` can be placed, as returned by `legal_placements`.
+	#[must_use]
+	pub fn count_legal_placements(&self, mino: Mino) -> usize {
+		self.legal_placements(mino).count()
 	}
 
 	#[must_use]
 	pub fn clamp_mino_position(
+		&self,
 		mino: Mino,
 		pos: (Coordinate, Coordinate),
 	) -> (Coordinate, Coordinate) {
 		let min_on_board = mino.min_point();
 		let max_on_board = mino.max_point();
 
-		let map = |pos: Coordinate, min: Coordinate, max: Coordinate| {
-			let min = min + pos;
-			let max = max + pos;
-			pos
+		// Widened so that positions near the limits of `Coordinate` can't overflow.
+		// The result is on the board, so it fits again.
+		let map = |pos: Coordinate, min: Coordinate, max: Coordinate, size: Coordinate| {
+			let (pos, size) = (i16::from(pos), i16::from(size));
+			let min = i16::from(min) + pos;
+			let max = i16::from(max) + pos;
+			let clamped = pos
 				+ if min < 0 {
 					-min
-				} else if max >= BOARD_SIZE {
-					BOARD_SIZE - max - 1
+				} else if max >= size {
+					size - max - 1
 				} else {
 					0
-				}
+				};
+			Coordinate::try_from(clamped).unwrap_or_else(|_| unreachable!())
 		};
 
 		(
-			map(pos.0, min_on_board.0, max_on_board.0),
-			map(pos.1, min_on_board.1, max_on_board.1),
+			map(pos.0, min_on_board.0, max_on_board.0, self.layout.width()),
+			map(pos.1, min_on_board.1, max_on_board.1, self.layout.height()),
 		)
 	}
 
@@ -283,14 +621,10 @@ impl Board {
 		mino: Mino,
 		mino_position: (Coordinate, Coordinate),
 	) -> Result<Board, OutOfBounds> {
-		let mut ret = Board::new();
-
-		for conflict in self.iter_conflicts(mino, mino_position) {
-			let conflict = conflict?;
-			ret.set(conflict, true);
-		}
-
-		Ok(ret)
+		Ok(Self {
+			layout: self.layout,
+			cells: self.cells & self.mino_mask(mino, mino_position)?,
+		})
 	}
 
 	#[allow(clippy::missing_errors_doc /* self-explanatory error type */)]
@@ -299,18 +633,11 @@ impl Board {
 		mino: Mino,
 		mino_position: (Coordinate, Coordinate),
 	) -> Result<(), PlaceError> {
-		let mut atomic = *self;
-
-		for position in Self::map_mino_coordinates(mino, mino_position) {
-			let position = position?;
-			if self.occupied(position) {
-				return Err(PlaceError::Conflicts);
-			}
-			atomic.set(position, true);
+		let mask = self.mino_mask(mino, mino_position)?;
+		if !(self.cells & mask).is_zero() {
+			return Err(PlaceError::Conflicts);
 		}
-
-		*self = atomic;
-
+		self.cells |= mask;
 		Ok(())
 	}
 
@@ -320,45 +647,363 @@ impl Board {
 		mino: Mino,
 		mino_position: (Coordinate, Coordinate),
 	) -> Result<impl Iterator<Item = WouldRemove> + Clone, PlaceError> {
-		let board_after = {
-			let mut board = *self;
-			board.place_at(mino, mino_position)?;
-			board
-		};
-		Ok(board_after.find_filled())
+		self
+			.place_and_clear(mino, mino_position)
+			.map(|(_, cleared)| cleared.iter())
 	}
 
-	fn find_filled(&self) -> impl Iterator<Item = WouldRemove> + Clone {
-		let board = *self;
-
-		let horizontal = (0..BOARD_SIZE)
-			.filter(move |&y| (0..BOARD_SIZE).all(move |x| board.occupied(Position::new_unchecked(x, y))))
-			.map(|y| WouldRemove::Horizontal { y });
-		let vertical = (0..BOARD_SIZE)
-			.filter(move |&x| (0..BOARD_SIZE).all(move |y| board.occupied(Position::new_unchecked(x, y))))
-			.map(|x| WouldRemove::Vertical { x });
-		let squares = SquareIndex::all()
-			.filter(move |square| square.all_within().all(|pos| board.occupied(pos)))
-			.map(|index| WouldRemove::Square { index });
+	/// Place `mino` and remove the regions it fills, returning the resulting board and the regions that were cleared.
+	///
+	/// This is equivalent to `place_at` followed by `remove_filled`, but doesn't modify `self`.
+	#[allow(clippy::missing_errors_doc /* self-explanatory error type */)]
+	pub fn place_and_clear(
+		&self,
+		mino: Mino,
+		mino_position: (Coordinate, Coordinate),
+	) -> Result<(Self, Cleared), PlaceError> {
+		let mut board = *self;
+		board.place_at(mino, mino_position)?;
+		let cleared = board.find_filled();
+		board.cells &= !cleared.removed;
+		Ok((board, cleared))
+	}
 
-		horizontal.chain(vertical).chain(squares)
+	fn find_filled(&self) -> Cleared {
+		let mut cleared = Cleared {
+			layout: self.layout,
+			filled: self.cells,
+			removed: Bits::ZERO,
+			count: 0,
+		};
+		for (_, mask) in self.layout.region_masks() {
+			if self.cells.contains(mask) {
+				cleared.removed |= mask;
+				cleared.count += 1;
+			}
+		}
+		cleared
 	}
 
 	/// Returns the number of `WouldRemove`s removed.
 	pub fn remove_filled(&mut self) -> usize {
-		let mut workspace = *self;
+		let cleared = self.find_filled();
+		self.cells &= !cleared.removed;
+		cleared.len()
+	}
+}
+
+/// The regions cleared by `Board::place_and_clear`.
+#[derive(Clone, Copy, Eq)]
+pub struct Cleared {
+	layout: &'static Layout,
+	/// The cells of the board before the regions were removed.
+	filled: Bits,
+	/// The union of the cleared regions.
+	removed: Bits,
+	count: u16,
+}
+
+impl PartialEq for Cleared {
+	fn eq(&self, other: &Self) -> bool {
+		self.filled == other.filled
+			&& self.removed == other.removed
+			&& self.count == other.count
+			&& same_layout(self.layout, other.layout)
+	}
+}
+
+impl Debug for Cleared {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		formatter
+			.debug_tuple("Cleared")
+			.field(&self.iter().collect::<Vec<_>>())
+			.finish()
+	}
+}
+
+impl Cleared {
+	/// The number of regions cleared.
+	#[inline]
+	#[must_use]
+	pub fn len(self) -> usize {
+		self.count.into()
+	}
+
+	#[inline]
+	#[must_use]
+	pub fn is_empty(self) -> bool {
+		self.count == 0
+	}
+
+	/// The regions cleared, in the order of `Layout::regions`.
+	pub fn iter(self) -> impl Iterator<Item = WouldRemove> + Clone {
+		let filled = self.filled;
+		self
+			.layout
+			.region_masks()
+			.filter(move |&(_, mask)| filled.contains(mask))
+			.map(|(region, _)| region)
+	}
+
+	/// The cells that were removed, which are in at least one of the cleared regions, in row-major order.
+	pub fn cells(self) -> impl Iterator<Item = Position> + Clone {
+		let layout = self.layout;
+		layout
+			.positions()
+			.filter(move |&position| self.removed.get(layout.cell_index(position)))
+	}
+}
 
-		let mut count = 0;
+#[test]
+fn test_remove_filled_custom_layout() {
+	let layout = Layout::new(6, 6, 2, 3).unwrap().intern();
+	let mut board = Board::with_layout(layout);
+	// Fill the bottom-right box and the rightmost column.
+	for position in layout.positions() {
+		if (position.x() >= 4 && position.y() >= 3) || position.x() == 5 {
+			board.set(position, true);
+		}
+	}
+	assert_eq!(board.remove_filled(), 2);
+	assert!(board.is_empty());
+
+	assert!(!board.is_in_bounds(Mino::all().next().unwrap(), (6, 0)));
+}
+
+#[test]
+fn test_board_text_format() {
+	let text = "\
+#........
+.#.......
+..#......
+.........
+.........
+.........
+.........
+.........
+########.";
+	let board: Board = text.parse().unwrap();
+	assert!(board.occupied(Position::new(2, 2).unwrap()));
+	assert!(!board.occupied(Position::new(8, 8).unwrap()));
+	assert_eq!(board.to_string(), text);
+	assert_eq!(board.one_line().to_string(), text.replace('\n', "/"));
+	assert_eq!(
+		board.one_line().to_string().parse::<Board>().unwrap(),
+		board
+	);
+
+	let layout = Layout::new(4, 2, 2, 1).unwrap().intern();
+	let board = Board::parse("#..#/.##.", layout).unwrap();
+	assert_eq!(board.to_string(), "#..#\n.##.");
+
+	assert_eq!(
+		Board::parse("#..#", layout),
+		Err(BoardParseError::WrongHeight {
+			expected: 2,
+			found: 1
+		})
+	);
+	assert_eq!(
+		Board::parse("#..#/.##", layout),
+		Err(BoardParseError::WrongWidth {
+			row: 2,
+			expected: 4,
+			found: 3
+		})
+	);
+	assert_eq!(
+		Board::parse("#..#/.#0.", layout),
+		Err(BoardParseError::InvalidChar {
+			row: 2,
+			column: 3,
+			char: '0'
+		})
+	);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_board_serde() {
+	let mut board = Board::new();
+	board.set(Position::new(1, 0).unwrap(), true);
+	let json = serde_json::to_string(&board).unwrap();
+	assert!(json.starts_with(r#"{"rows":[".#.......",".........""#));
+	assert_eq!(serde_json::from_str::<Board>(&json).unwrap(), board);
+
+	let layout = Layout::new(4, 2, 2, 1).unwrap().intern();
+	let board = Board::parse("#..#/.##.", layout).unwrap();
+	let json = serde_json::to_string(&board).unwrap();
+	assert_eq!(
+		json,
+		r##"{"layout":{"regions":[[0,0,1,1],[2,2,3,3]]},"rows":["#..#",".##."]}"##
+	);
+	assert_eq!(serde_json::from_str::<Board>(&json).unwrap(), board);
+	assert!(serde_json::from_str::<Board>(r##"{"rows":["#"]}"##).is_err());
+
+	let position = Position::new(3, 4).unwrap();
+	assert_eq!(
+		serde_json::to_string(&position).unwrap(),
+		r#"{"x":3,"y":4}"#
+	);
+	assert!(serde_json::from_str::<Position>(r#"{"x":3,"y":16}"#).is_err());
+	assert!(serde_json::from_str::<Position>(r#"{"x":-1,"y":0}"#).is_err());
+
+	let would_remove = WouldRemove::Vertical { x: 2 };
+	assert_eq!(
+		serde_json::to_string(&would_remove).unwrap(),
+		r#"{"vertical":{"x":2}}"#
+	);
+	assert!(serde_json::from_str::<WouldRemove>(r#"{"horizontal":{"y":20}}"#).is_err());
+}
+
+#[test]
+fn test_masks_match_squares() {
+	let mut rng = crate::rng::Rng::new(5);
+	for layout in [
+		Layout::standard(),
+		Layout::new(16, 12, 4, 4).unwrap().intern(),
+	] {
+		let mut board = Board::with_layout(layout);
+		for position in layout.positions() {
+			board.set(position, rng.below(3) == 0);
+		}
 
-		for filled in self.find_filled() {
-			count += 1;
-			for position in filled.iter() {
-				workspace.set(position, false);
+		for mino in Mino::all().flat_map(|mino| [mino, mino.rotate_cw_90()]) {
+			for y in -6..layout.height() + 2 {
+				for x in -6..layout.width() + 2 {
+					let positions: Option<Vec<Position>> = mino
+						.iter()
+						.map(|(dx, dy)| layout.position(x + dx, y + dy))
+						.collect();
+					assert_eq!(board.is_in_bounds(mino, (x, y)), positions.is_some());
+
+					let mut placed = board;
+					let can_place = placed.place_at(mino, (x, y)).is_ok();
+					let expected = positions
+						.is_some_and(|positions| positions.iter().all(|&position| !board.occupied(position)));
+					assert_eq!(can_place, expected);
+					assert_eq!(
+						board
+							.legal_placements(mino)
+							.any(|position| position == (x, y)),
+						expected
+					);
+					if can_place {
+						for (dx, dy) in mino.iter() {
+							assert!(placed.occupied(Position::new_unchecked(x + dx, y + dy)));
+						}
+					}
+				}
 			}
 		}
+	}
+}
+
+#[test]
+fn test_legal_placements() {
+	let mut board = Board::new();
+	let single: Mino = "#".parse().unwrap();
+	let bar: Mino = "###".parse().unwrap();
+	assert_eq!(board.count_legal_placements(single), 81);
+	assert_eq!(board.count_legal_placements(bar), 7 * 9);
+	assert_eq!(board.legal_placements(bar).next(), Some((0, 0)));
+
+	// Leave only the top row and a single cell below it free.
+	for position in board.layout().positions() {
+		board.set(
+			position,
+			position.y() > 0 && position != Position::new(4, 1).unwrap(),
+		);
+	}
+	assert_eq!(
+		board.legal_placements(bar).collect::<Vec<_>>(),
+		(0..7).map(|x| (x, 0)).collect::<Vec<_>>()
+	);
+	assert_eq!(board.count_legal_placements(single), 10);
+	assert_eq!(board.count_legal_placements("#\n#".parse().unwrap()), 1);
+	assert_eq!(board.count_legal_placements("##\n##".parse().unwrap()), 0);
+
+	// Positions near the limits of `Coordinate` are out of bounds or clamped without overflowing.
+	let padded: Mino = ".....\n.....\n..#..\n.....\n.....".parse().unwrap();
+	for position in [(0, 126), (126, 0), (127, 127), (-128, -128)] {
+		assert!(!board.is_in_bounds(padded, position));
+	}
+	assert_eq!(board.clamp_mino_position(padded, (127, 127)), (6, 6));
+	assert_eq!(board.clamp_mino_position(padded, (-128, -128)), (-2, -2));
+}
 
-		*self = workspace;
+#[test]
+fn test_place_and_clear() {
+	for name in Layout::builtin_names() {
+		let layout = Layout::builtin(name).unwrap();
+		assert_eq!(layout.num_regions(), layout.regions().count(), "{name}");
+		for (region, mask) in layout.region_masks() {
+			let positions = layout
+				.positions()
+				.filter(|&position| mask.get(layout.cell_index(position)));
+			assert!(positions.eq(region.iter(layout)), "{name}: {region:?}");
+		}
 
-		count
+		// Fill everything but the top-left cell, then play a single square there.
+		let mut board = Board::with_layout(layout);
+		for position in layout.positions().skip(1) {
+			board.set(position, true);
+		}
+		let single: Mino = "#".parse().unwrap();
+		let (after, cleared) = board.place_and_clear(single, (0, 0)).unwrap();
+		assert_eq!(cleared.len(), layout.num_regions(), "{name}");
+		assert!(after.is_empty());
+
+		let mut expected = board;
+		expected.place_at(single, (0, 0)).unwrap();
+		assert_eq!(expected.remove_filled(), cleared.len());
+		assert_eq!(expected, after);
 	}
+
+	let board: Board = "\
+########.
+.........
+.........
+.........
+.........
+.........
+.........
+.........
+........."
+		.parse()
+		.unwrap();
+	let single: Mino = "#".parse().unwrap();
+	let (after, cleared) = board.place_and_clear(single, (8, 0)).unwrap();
+	assert!(after.is_empty());
+	assert_eq!(
+		cleared.iter().collect::<Vec<_>>(),
+		[WouldRemove::Horizontal { y: 0 }]
+	);
+	assert_eq!(format!("{cleared:?}"), "Cleared([Horizontal { y: 0 }])");
+	let (after, cleared) = board.place_and_clear(single, (8, 1)).unwrap();
+	assert!(cleared.is_empty() && !after.is_empty());
+	assert!(board.place_and_clear(single, (0, 0)).is_err());
+}
+
+#[test]
+fn test_board_eq_and_hash() {
+	use std::collections::hash_map::DefaultHasher;
+
+	let hash = |board: &Board| {
+		let mut hasher = DefaultHasher::new();
+		board.hash(&mut hasher);
+		hasher.finish()
+	};
+
+	// A layout equal to the standard one that wasn't interned.
+	let leaked: &'static Layout = Box::leak(Box::new(Layout::standard().clone()));
+	let board = Board::new();
+	assert_eq!(board, Board::with_layout(leaked));
+	assert_eq!(hash(&board), hash(&Board::with_layout(leaked)));
+
+	let jigsaw = Board::with_layout(Layout::builtin("jigsaw-1").unwrap());
+	assert_ne!(board, jigsaw);
+	let mut occupied = board;
+	occupied.set(Position::new_unchecked(0, 0), true);
+	assert_ne!(board, occupied);
 }
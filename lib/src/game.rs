@@ -0,0 +1,801 @@
+use std::collections::VecDeque;
+use std::fmt::{self, Display, Formatter};
+
+use crate::board::{Board, PlaceError, Position, WouldRemove};
+use crate::generator::MinoGenerator;
+#[cfg(test)]
+use crate::generator::RepeatGenerator;
+#[cfg(test)]
+use crate::generator::UniformGenerator;
+use crate::mino::Mino;
+use crate::scoring::{ClassicScoring, ScoringRule};
+use crate::strategy::Turn;
+use crate::util::Coordinate;
+
+/// The number of minos dealt at once.
+pub const BATCH_SIZE: usize = 3;
+
+/// The mino currently selected for placement by a frontend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Selection {
+	/// The index of the mino within the batch.
+	pub index: usize,
+	/// The position within the board of the top-left corner of the mino's container.
+	pub position: (Coordinate, Coordinate),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum PlayError {
+	/// There is no mino at the given index, either because the index is out of range or because the mino has already been placed.
+	NoMino,
+	Place(PlaceError),
+}
+
+impl Display for PlayError {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::NoMino => formatter.write_str("there is no mino at that index of the batch"),
+			Self::Place(error) => Display::fmt(error, formatter),
+		}
+	}
+}
+
+impl std::error::Error for PlayError {}
+
+impl From<PlaceError> for PlayError {
+	fn from(error: PlaceError) -> Self {
+		Self::Place(error)
+	}
+}
+
+/// Everything that happened as the result of placing a mino, as returned by `Game::play`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlacementOutcome {
+	/// The mino that was placed.
+	pub mino: Mino,
+	/// The position the mino was placed at.
+	pub position: (Coordinate, Coordinate),
+	/// The cells covered by the mino, in row-major order.
+	pub placed: Vec<Position>,
+	/// The rows, columns and squares that were filled by the placement and cleared.
+	pub cleared: Vec<WouldRemove>,
+	/// The cells that were removed by clearing, in row-major order.
+	/// These can include cells in `placed`.
+	pub removed: Vec<Position>,
+	/// The points awarded for the placement.
+	pub points: u32,
+	/// Whether the board was left completely empty.
+	pub board_emptied: bool,
+}
+
+/// The state of a game that is restored by `Game::undo` and `Game::redo`.
+#[derive(Debug, Clone)]
+struct Snapshot<G> {
+	/// Only stored around placements that exhaust the batch, since the generator doesn't change otherwise.
+	generator: Option<G>,
+	board: Board,
+	minos: [Option<Mino>; BATCH_SIZE],
+	selected: Option<Selection>,
+	score: u32,
+	last_points: u32,
+	streak: u32,
+	lost: bool,
+}
+
+/// The rules of the game, independent of any frontend.
+///
+/// Minos are dealt by the generator `G` whenever the current batch is exhausted,
+/// and placements are scored by the scoring rule `S`.
+///
+/// Placements can be undone and redone, up to an optional limit; see `Game::with_undo_limit`.
+/// Undoing the placement that exhausted a batch also rewinds the generator, which is why it must be `Clone`.
+///
+/// With the `serde` feature, the whole state of the game can be serialized, including the generator and scoring rule.
+/// The undo limit is serialized but the undo history isn't.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(
+		try_from = "GameRepr<G, S>",
+		// The skipped histories would otherwise require `G: Default`.
+		bound(
+			serialize = "G: serde::Serialize, S: serde::Serialize",
+			deserialize = "G: serde::Deserialize<'de>, S: serde::Deserialize<'de>"
+		)
+	)
+)]
+pub struct Game<G, S = ClassicScoring> {
+	generator: G,
+	scoring: S,
+	board: Board,
+	minos: [Option<Mino>; BATCH_SIZE],
+	/// Invariant: when `selected` is `Some`, `minos[selected.index]` is `Some`.
+	selected: Option<Selection>,
+
+	score: u32,
+	last_points: u32,
+	/// The number of placements in a row, up to the most recent one, that cleared something.
+	streak: u32,
+	lost: bool,
+
+	/// `None` if unlimited.
+	undo_limit: Option<usize>,
+	/// The states before each placement that can be undone, most recent last.
+	#[cfg_attr(feature = "serde", serde(skip))]
+	undo_history: VecDeque<Snapshot<G>>,
+	/// The states after each placement that was undone, most recently undone last.
+	#[cfg_attr(feature = "serde", serde(skip))]
+	redo_history: Vec<Snapshot<G>>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct GameRepr<G, S> {
+	generator: G,
+	scoring: S,
+	board: Board,
+	minos: [Option<Mino>; BATCH_SIZE],
+	selected: Option<Selection>,
+	score: u32,
+	last_points: u32,
+	streak: u32,
+	lost: bool,
+	undo_limit: Option<usize>,
+}
+
+#[cfg(feature = "serde")]
+impl<G, S> TryFrom<GameRepr<G, S>> for Game<G, S> {
+	type Error = &'static str;
+
+	fn try_from(repr: GameRepr<G, S>) -> Result<Self, Self::Error> {
+		if repr.minos.iter().all(Option::is_none) {
+			return Err("the batch must not be empty");
+		}
+		if let Some(selected) = repr.selected {
+			if repr.minos.get(selected.index).copied().flatten().is_none() {
+				return Err("the selected mino must be in the batch");
+			}
+		}
+		Ok(Self {
+			generator: repr.generator,
+			scoring: repr.scoring,
+			board: repr.board,
+			minos: repr.minos,
+			selected: repr.selected,
+			score: repr.score,
+			last_points: repr.last_points,
+			streak: repr.streak,
+			lost: repr.lost,
+			undo_limit: repr.undo_limit,
+			undo_history: VecDeque::new(),
+			redo_history: Vec::new(),
+		})
+	}
+}
+
+impl<G: MinoGenerator + Clone> Game<G> {
+	/// Start a game on an empty board with the standard layout, scored by `ClassicScoring`.
+	#[must_use]
+	pub fn new(generator: G) -> Self {
+		Self::with_board(generator, Board::new())
+	}
+
+	/// Start a game on `board`, which determines the layout and may already have occupied squares.
+	#[must_use]
+	pub fn with_board(generator: G, board: Board) -> Self {
+		let mut ret = Self {
+			generator,
+			scoring: ClassicScoring,
+			board,
+			minos: [None; BATCH_SIZE],
+			selected: None,
+
+			score: 0,
+			last_points: 0,
+			streak: 0,
+			lost: false,
+
+			undo_limit: None,
+			undo_history: VecDeque::new(),
+			redo_history: Vec::new(),
+		};
+
+		ret.refill();
+
+		ret
+	}
+}
+
+impl<G: MinoGenerator + Clone, S: ScoringRule> Game<G, S> {
+	/// Score placements with `scoring` from now on.
+	#[must_use]
+	pub fn with_scoring<T: ScoringRule>(self, scoring: T) -> Game<G, T> {
+		Game {
+			generator: self.generator,
+			scoring,
+			board: self.board,
+			minos: self.minos,
+			selected: self.selected,
+			score: self.score,
+			last_points: self.last_points,
+			streak: self.streak,
+			lost: self.lost,
+			undo_limit: self.undo_limit,
+			undo_history: self.undo_history,
+			redo_history: self.redo_history,
+		}
+	}
+
+	/// Only allow the most recent `limit` placements to be undone, or any number if `limit` is `None`.
+	/// A limit of zero disables undo, for example for ranked play.
+	#[must_use]
+	pub fn with_undo_limit(mut self, limit: Option<usize>) -> Self {
+		self.undo_limit = limit;
+		self.trim_undo_history();
+		self
+	}
+
+	fn trim_undo_history(&mut self) {
+		if let Some(limit) = self.undo_limit {
+			while self.undo_history.len() > limit {
+				self.undo_history.pop_front();
+			}
+		}
+	}
+
+	/// A copy of the game that can't undo or redo anything, which is much cheaper than `clone` with a long history.
+	pub(crate) fn clone_without_history(&self) -> Self
+	where
+		S: Clone,
+	{
+		Self {
+			generator: self.generator.clone(),
+			scoring: self.scoring.clone(),
+			board: self.board,
+			minos: self.minos,
+			selected: self.selected,
+			score: self.score,
+			last_points: self.last_points,
+			streak: self.streak,
+			lost: self.lost,
+			undo_limit: self.undo_limit,
+			undo_history: VecDeque::new(),
+			redo_history: Vec::new(),
+		}
+	}
+
+	/// The current state, including the generator if `with_generator` is `true`.
+	fn snapshot(&self, with_generator: bool) -> Snapshot<G> {
+		Snapshot {
+			generator: with_generator.then(|| self.generator.clone()),
+			board: self.board,
+			minos: self.minos,
+			selected: self.selected,
+			score: self.score,
+			last_points: self.last_points,
+			streak: self.streak,
+			lost: self.lost,
+		}
+	}
+
+	fn restore(&mut self, snapshot: Snapshot<G>) {
+		if let Some(generator) = snapshot.generator {
+			self.generator = generator;
+		}
+		Snapshot {
+			generator: _,
+			board: self.board,
+			minos: self.minos,
+			selected: self.selected,
+			score: self.score,
+			last_points: self.last_points,
+			streak: self.streak,
+			lost: self.lost,
+		} = snapshot;
+	}
+
+	/// Take back the most recent placement that hasn't been undone yet, restoring the board, batch, selection and score from before it.
+	///
+	/// Returns `false` if there is nothing to undo.
+	pub fn undo(&mut self) -> bool {
+		let Some(snapshot) = self.undo_history.pop_back() else {
+			return false;
+		};
+		self
+			.redo_history
+			.push(self.snapshot(snapshot.generator.is_some()));
+		self.restore(snapshot);
+		true
+	}
+
+	/// Play the most recently undone placement again.
+	///
+	/// Returns `false` if there is nothing to redo; placing a mino discards the placements that could be redone.
+	pub fn redo(&mut self) -> bool {
+		let Some(snapshot) = self.redo_history.pop() else {
+			return false;
+		};
+		self
+			.undo_history
+			.push_back(self.snapshot(snapshot.generator.is_some()));
+		self.restore(snapshot);
+		true
+	}
+
+	#[inline]
+	#[must_use]
+	pub fn can_undo(&self) -> bool {
+		!self.undo_history.is_empty()
+	}
+
+	#[inline]
+	#[must_use]
+	pub fn can_redo(&self) -> bool {
+		!self.redo_history.is_empty()
+	}
+
+	/// The maximum number of placements that can be undone, or `None` if unlimited.
+	#[inline]
+	#[must_use]
+	pub fn undo_limit(&self) -> Option<usize> {
+		self.undo_limit
+	}
+
+	/// Deal a new batch from the generator and select the first mino that can be placed.
+	///
+	/// This happens automatically when the batch is exhausted.
+	/// Any minos remaining from the previous batch are discarded.
+	pub fn refill(&mut self) {
+		self.minos = self.generator.next_batch(&self.board).map(Some);
+		self.update_lost();
+		self.select_next();
+	}
+
+	fn update_lost(&mut self) {
+		self.lost = self
+			.minos
+			.iter()
+			.flatten()
+			.all(|&mino| !self.board.can_place_anywhere(mino));
+	}
+
+	/// Select the mino at `index`, placing it so that its top-left square is at the top-left of the board.
+	///
+	/// Returns `false` and leaves the selection unchanged if there is no mino at `index`.
+	pub fn select(&mut self, index: usize) -> bool {
+		let Some(mino) = self.mino(index) else {
+			return false;
+		};
+		let min_point = mino.min_point();
+		self.selected = Some(Selection {
+			index,
+			position: (-min_point.0, -min_point.1),
+		});
+		true
+	}
+
+	/// Select the first mino that can be placed somewhere on the board, if any.
+	pub fn select_next(&mut self) {
+		if let Some(next) = (0..BATCH_SIZE).find(|&index| self.can_place(index)) {
+			self.select(next);
+		}
+	}
+
+	#[inline]
+	#[must_use]
+	pub fn selected(&self) -> Option<Selection> {
+		self.selected
+	}
+
+	/// The selected mino along with its position.
+	#[must_use]
+	pub fn selected_mino(&self) -> Option<(Mino, (Coordinate, Coordinate))> {
+		let selected = self.selected?;
+		Some((self.minos[selected.index]?, selected.position))
+	}
+
+	/// Move the selected mino by the given offset.
+	///
+	/// If the mino would leave the board, it wraps around to the opposite edge instead.
+	pub fn move_selected(&mut self, dx: Coordinate, dy: Coordinate) {
+		let Some((mino, position)) = self.selected_mino() else {
+			return;
+		};
+
+		let new_position = (position.0 + dx, position.1 + dy);
+		let new_position = if self.board.is_in_bounds(mino, new_position) {
+			new_position
+		} else {
+			let mut new_position = position;
+			loop {
+				let check = (new_position.0 - dx, new_position.1 - dy);
+				if self.board.is_in_bounds(mino, check) {
+					new_position = check;
+				} else {
+					break;
+				}
+			}
+			new_position
+		};
+
+		if let Some(selected) = &mut self.selected {
+			selected.position = new_position;
+		}
+	}
+
+	/// Move the selected mino to `position`, clamping it so that it stays within the board.
+	pub fn set_selected_position(&mut self, position: (Coordinate, Coordinate)) {
+		let Some((mino, _)) = self.selected_mino() else {
+			return;
+		};
+		if let Some(selected) = &mut self.selected {
+			selected.position = self.board.clamp_mino_position(mino, position);
+		}
+	}
+
+	/// Place the selected mino at its current position.
+	///
+	/// Returns the number of points awarded for the placement.
+	#[allow(clippy::missing_errors_doc /* see `place` */)]
+	pub fn place_selected(&mut self) -> Result<u32, PlayError> {
+		let Some(selected) = self.selected else {
+			return Err(PlayError::NoMino);
+		};
+		self.place(selected.index, selected.position)
+	}
+
+	/// Like `play`, but only returns the number of points awarded for the placement.
+	#[allow(clippy::missing_errors_doc /* see `play` */)]
+	pub fn place(
+		&mut self,
+		index: usize,
+		position: (Coordinate, Coordinate),
+	) -> Result<u32, PlayError> {
+		self.play(index, position).map(|outcome| outcome.points)
+	}
+
+	/// Place the mino at `index` within the batch at `position`, clear any filled features, and update the score and streak.
+	///
+	/// # Errors
+	///
+	/// Fails if there is no mino at `index` or the mino cannot be placed at `position`.
+	/// The game is left unchanged in that case.
+	pub fn play(
+		&mut self,
+		index: usize,
+		position: (Coordinate, Coordinate),
+	) -> Result<PlacementOutcome, PlayError> {
+		let mino = self.mino(index).ok_or(PlayError::NoMino)?;
+		let (board, cleared) = self.board.place_and_clear(mino, position)?;
+		let removed = cleared.cells().collect();
+		let cleared: Vec<WouldRemove> = cleared.iter().collect();
+
+		if self.undo_limit != Some(0) {
+			let exhausts_batch = self.minos.iter().flatten().count() == 1;
+			self.undo_history.push_back(self.snapshot(exhausts_batch));
+			self.trim_undo_history();
+		}
+		self.redo_history.clear();
+
+		self.board = board;
+		self.minos[index] = None;
+		self.selected = None;
+
+		let points = self.scoring.points(mino, &cleared, self.streak);
+		self.streak = if cleared.is_empty() {
+			0
+		} else {
+			self.streak + 1
+		};
+		self.last_points = points;
+		self.score += points;
+
+		let outcome = PlacementOutcome {
+			mino,
+			position,
+			placed: mino
+				.iter()
+				.map(|(dx, dy)| Position::new_unchecked(position.0 + dx, position.1 + dy))
+				.collect(),
+			cleared,
+			removed,
+			points,
+			board_emptied: self.board.is_empty(),
+		};
+
+		if self.minos.iter().all(Option::is_none) {
+			self.refill();
+		} else {
+			self.update_lost();
+			self.select_next();
+		}
+
+		Ok(outcome)
+	}
+
+	#[inline]
+	#[must_use]
+	pub fn generator(&self) -> &G {
+		&self.generator
+	}
+
+	#[inline]
+	#[must_use]
+	pub fn scoring(&self) -> &S {
+		&self.scoring
+	}
+
+	#[inline]
+	#[must_use]
+	pub fn board(&self) -> &Board {
+		&self.board
+	}
+
+	/// The mino at `index` within the batch, if it has not been placed yet.
+	#[inline]
+	#[must_use]
+	pub fn mino(&self, index: usize) -> Option<Mino> {
+		self.minos.get(index).copied().flatten()
+	}
+
+	#[inline]
+	#[must_use]
+	pub fn minos(&self) -> [Option<Mino>; BATCH_SIZE] {
+		self.minos
+	}
+
+	/// Whether the mino at `index` exists and can be placed somewhere on the board.
+	#[must_use]
+	pub fn can_place(&self, index: usize) -> bool {
+		self
+			.mino(index)
+			.is_some_and(|mino| self.board.can_place_anywhere(mino))
+	}
+
+	/// Whether none of the remaining minos in the batch can be placed.
+	#[inline]
+	#[must_use]
+	pub fn lost(&self) -> bool {
+		self.lost
+	}
+
+	#[inline]
+	#[must_use]
+	pub fn score(&self) -> u32 {
+		self.score
+	}
+
+	/// The number of points awarded for the most recent placement.
+	#[inline]
+	#[must_use]
+	pub fn last_points(&self) -> u32 {
+		self.last_points
+	}
+
+	/// The number of placements in a row, up to the most recent one, that cleared at least one feature.
+	#[inline]
+	#[must_use]
+	pub fn streak(&self) -> u32 {
+		self.streak
+	}
+
+	/// What a `Strategy` needs to choose the next placement.
+	#[must_use]
+	pub fn turn(&self) -> Turn<'_> {
+		Turn {
+			board: &self.board,
+			minos: self.minos,
+			score: self.score,
+			streak: self.streak,
+			scoring: &self.scoring,
+		}
+	}
+}
+
+#[test]
+fn test_place_and_refill() {
+	let single = Mino::all().find(|mino| mino.num_squares() == 1).unwrap();
+	let mut game = Game::new(RepeatGenerator(vec![single]));
+	assert_eq!(game.selected().map(|selected| selected.index), Some(0));
+
+	let origin = {
+		let min_point = single.min_point();
+		(-min_point.0, -min_point.1)
+	};
+	assert_eq!(game.place(0, origin).unwrap(), 1);
+	assert!(matches!(
+		game.place(1, origin),
+		Err(PlayError::Place(PlaceError::Conflicts))
+	));
+	assert!(matches!(game.place(0, origin), Err(PlayError::NoMino)));
+	assert_eq!(game.selected().map(|selected| selected.index), Some(1));
+
+	game.place_selected().unwrap_err();
+	game.move_selected(1, 0);
+	assert_eq!(game.place_selected().unwrap(), 1);
+	game.move_selected(2, 0);
+	assert_eq!(game.place_selected().unwrap(), 1);
+	assert_eq!(game.score(), 3);
+
+	// The batch was exhausted, so a new one was dealt.
+	assert_eq!(game.minos(), [Some(single); BATCH_SIZE]);
+	assert!(!game.lost());
+}
+
+#[test]
+fn test_clear_scoring() {
+	let horizontal_bar = |length: usize| {
+		Mino::all()
+			.find(|mino| mino.num_squares() == length && mino.min_point().1 == mino.max_point().1)
+			.unwrap()
+	};
+	let bar5 = horizontal_bar(5);
+	let bar4 = horizontal_bar(4);
+	let mut game = Game::new(RepeatGenerator(vec![bar5, bar4, bar4]));
+
+	let origin = |mino: Mino| {
+		let min_point = mino.min_point();
+		(-min_point.0, -min_point.1)
+	};
+	assert_eq!(game.place(0, origin(bar5)).unwrap(), 5);
+	let bar4_origin = origin(bar4);
+	assert_eq!(
+		game.place(1, (bar4_origin.0 + 5, bar4_origin.1)).unwrap(),
+		4 + 3
+	);
+	assert!(game.board().is_empty());
+	assert_eq!(game.score(), 12);
+	assert_eq!(game.last_points(), 7);
+	assert_eq!(game.streak(), 1);
+
+	let mut game = game.with_scoring(crate::scoring::TenTenScoring);
+	assert_eq!(game.place(2, bar4_origin).unwrap(), 4);
+	assert_eq!(game.streak(), 0);
+	assert_eq!(game.score(), 16);
+}
+
+#[test]
+fn test_placement_outcome() {
+	let single: Mino = "#".parse().unwrap();
+	let corner: Mino = "##\n#.".parse().unwrap();
+	let board: Board = "\
+.######..
+.........
+.........
+.........
+.........
+.........
+.........
+.........
+........."
+		.parse()
+		.unwrap();
+	let mut game = Game::with_board(RepeatGenerator(vec![corner, single]), board);
+
+	let outcome = game.play(0, (7, 0)).unwrap();
+	assert_eq!(outcome.mino, corner);
+	assert_eq!(outcome.position, (7, 0));
+	let position = |x, y| Position::new(x, y).unwrap();
+	assert_eq!(
+		outcome.placed,
+		[position(7, 0), position(8, 0), position(7, 1)]
+	);
+	assert!(outcome.cleared.is_empty() && outcome.removed.is_empty());
+	assert_eq!(outcome.points, 3);
+	assert!(!outcome.board_emptied);
+
+	let outcome = game.play(1, (0, 0)).unwrap();
+	assert_eq!(outcome.cleared, [WouldRemove::Horizontal { y: 0 }]);
+	assert_eq!(
+		outcome.removed,
+		(0..9).map(|x| position(x, 0)).collect::<Vec<_>>()
+	);
+	assert_eq!(outcome.points, 1 + 3);
+	assert!(!outcome.board_emptied);
+	assert!(game.board().occupied(position(7, 1)));
+}
+
+#[test]
+fn test_streak() {
+	let single: Mino = "#".parse().unwrap();
+	let board: Board = "\
+########.
+########.
+.........
+.........
+.........
+.........
+.........
+.........
+........."
+		.parse()
+		.unwrap();
+	let mut game = Game::with_board(RepeatGenerator(vec![single]), board);
+
+	assert_eq!(game.place(0, (8, 0)).unwrap(), 1 + 3);
+	assert_eq!(game.streak(), 1);
+	// The second clear in a row gets double the points for clearing.
+	assert_eq!(game.place(1, (8, 1)).unwrap(), 1 + 3 * 2);
+	assert_eq!(game.streak(), 2);
+	assert_eq!(game.place(2, (0, 0)).unwrap(), 1);
+	assert_eq!(game.streak(), 0);
+}
+
+#[test]
+fn test_undo_redo() {
+	let single: Mino = "#".parse().unwrap();
+	let mut game = Game::new(RepeatGenerator(vec![single]));
+	assert!(!game.undo() && !game.redo());
+
+	game.place(0, (0, 0)).unwrap();
+	game.place(1, (1, 0)).unwrap();
+	let after_two = (*game.board(), game.minos(), game.score());
+	game.place(2, (2, 0)).unwrap();
+	// The batch was refilled.
+	assert_eq!(game.minos(), [Some(single); BATCH_SIZE]);
+
+	assert!(game.undo());
+	assert_eq!((*game.board(), game.minos(), game.score()), after_two);
+	assert_eq!(game.selected().map(|selected| selected.index), Some(2));
+	assert!(game.undo() && game.undo() && !game.undo());
+	assert!(game.board().is_empty());
+	assert_eq!(game.score(), 0);
+
+	assert!(game.redo() && game.redo());
+	assert_eq!((*game.board(), game.minos(), game.score()), after_two);
+	assert_eq!(game.last_points(), 1);
+
+	// Placing a mino discards the redo history.
+	game.place(2, (5, 5)).unwrap();
+	assert!(!game.can_redo());
+
+	let mut game = game.with_undo_limit(Some(1));
+	assert!(game.undo() && !game.undo());
+
+	let mut game = game.with_undo_limit(Some(0));
+	game.place(2, (8, 8)).unwrap();
+	assert!(!game.can_undo());
+	// Undoing the placement that exhausted a batch rewinds the generator, so the same batch is dealt again.
+	let mut game = Game::new(UniformGenerator::new(7));
+	for index in 0..BATCH_SIZE {
+		let mino = game.mino(index).unwrap();
+		let position = game.board().legal_placements(mino).last().unwrap();
+		game.place(index, position).unwrap();
+	}
+	let next_batch = game.minos();
+	assert!(game.undo());
+	let mino = game.mino(2).unwrap();
+	let position = game.board().legal_placements(mino).next().unwrap();
+	game.place(2, position).unwrap();
+	assert_eq!(game.minos(), next_batch);
+	assert!(game.undo() && game.redo());
+	assert_eq!(game.minos(), next_batch);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_game_serde() {
+	use crate::generator::{AnyGenerator, GeneratorKind};
+
+	let mut game = Game::new(AnyGenerator::new(GeneratorKind::Hard { hardness: 2 }, 7).survivable());
+	game.place_selected().unwrap();
+	let json = serde_json::to_string(&game).unwrap();
+	let mut restored: Game<AnyGenerator> = serde_json::from_str(&json).unwrap();
+	assert_eq!(restored.board(), game.board());
+	assert_eq!(restored.minos(), game.minos());
+	assert_eq!(restored.selected(), game.selected());
+	assert_eq!(restored.score(), game.score());
+
+	// The generator state is restored too, so both games continue identically.
+	for _ in 0..10 {
+		assert_eq!(restored.place_selected().ok(), game.place_selected().ok());
+		assert_eq!(restored.minos(), game.minos());
+	}
+
+	let mut invalid: serde_json::Value = serde_json::from_str(&json).unwrap();
+	invalid["selected"]["index"] = 7.into();
+	assert!(serde_json::from_value::<Game<AnyGenerator>>(invalid).is_err());
+}
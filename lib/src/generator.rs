@@ -0,0 +1,726 @@
+use crate::board::Board;
+use crate::game::BATCH_SIZE;
+#[cfg(test)]
+use crate::layout::Layout;
+use crate::mino::Mino;
+use crate::mino_set::MinoSet;
+use crate::rng::Rng;
+
+/// A source of minos to deal to the player.
+pub trait MinoGenerator {
+	/// Generate the next mino to be dealt onto `board`.
+	fn next_mino(&mut self, board: &Board) -> Mino;
+
+	/// Generate a whole batch to be dealt onto `board`.
+	///
+	/// By default this generates each mino independently.
+	fn next_batch(&mut self, board: &Board) -> [Mino; BATCH_SIZE] {
+		std::array::from_fn(|_| self.next_mino(board))
+	}
+}
+
+impl<G: MinoGenerator + ?Sized> MinoGenerator for &mut G {
+	fn next_mino(&mut self, board: &Board) -> Mino {
+		(**self).next_mino(board)
+	}
+
+	fn next_batch(&mut self, board: &Board) -> [Mino; BATCH_SIZE] {
+		(**self).next_batch(board)
+	}
+}
+
+impl<G: MinoGenerator + ?Sized> MinoGenerator for Box<G> {
+	fn next_mino(&mut self, board: &Board) -> Mino {
+		(**self).next_mino(board)
+	}
+
+	fn next_batch(&mut self, board: &Board) -> [Mino; BATCH_SIZE] {
+		(**self).next_batch(board)
+	}
+}
+
+/// The number of combinations of flips and rotations, as returned by `Mino::transforms`.
+const NUM_TRANSFORMS: usize = 8;
+
+/// Pick one of the orientations of `mino` uniformly.
+fn random_orientation(mino: Mino, rng: &mut Rng) -> Mino {
+	let orientations = mino.orientations();
+	orientations[rng.below(orientations.len())]
+}
+
+fn random_tile(set: &MinoSet, rng: &mut Rng) -> Mino {
+	set.minos()[rng.below(set.len())]
+}
+
+/// Selects each mino independently and uniformly from the tile set, then picks one of its orientations uniformly.
+///
+/// The same seed always produces the same sequence of minos.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UniformGenerator {
+	rng: Rng,
+	set: MinoSet,
+}
+
+impl UniformGenerator {
+	/// Use the built-in tiles.
+	#[inline]
+	#[must_use]
+	pub fn new(seed: u64) -> Self {
+		Self {
+			rng: Rng::new(seed),
+			set: MinoSet::builtin(),
+		}
+	}
+
+	/// Pick from the tiles of `set` instead.
+	#[inline]
+	#[must_use]
+	pub fn with_set(self, set: MinoSet) -> Self {
+		Self { set, ..self }
+	}
+}
+
+impl MinoGenerator for UniformGenerator {
+	fn next_mino(&mut self, _board: &Board) -> Mino {
+		let mino = random_tile(&self.set, &mut self.rng);
+		random_orientation(mino, &mut self.rng)
+	}
+}
+
+/// Deals minos from a shuffled bag containing every tile in the tile set, refilling the bag once it is empty.
+///
+/// This guarantees that each tile appears at least once in every `2 * bag_size()` consecutive minos,
+/// where the bag holds `copies * set.len()` minos, or eight times as many when orientations are bagged.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BagGenerator {
+	rng: Rng,
+	set: MinoSet,
+	#[cfg_attr(feature = "serde", serde(deserialize_with = "deserialize_copies"))]
+	copies: usize,
+	bag_orientations: bool,
+	/// Minos are dealt from the end.
+	bag: Vec<Mino>,
+}
+
+impl BagGenerator {
+	/// Each bag contains `copies` copies of every tile.
+	/// If `bag_orientations` is `true`, every orientation of each tile is also put in the bag,
+	/// so orientations are subject to the same guarantees as tiles; otherwise orientations are random.
+	///
+	/// # Panics
+	///
+	/// Panics if `copies` is zero.
+	#[must_use]
+	pub fn new(seed: u64, copies: usize, bag_orientations: bool) -> Self {
+		assert!(
+			copies > 0,
+			"the bag must contain at least one copy of each tile"
+		);
+		Self {
+			rng: Rng::new(seed),
+			set: MinoSet::builtin(),
+			copies,
+			bag_orientations,
+			bag: Vec::new(),
+		}
+	}
+
+	/// Deal the tiles of `set` instead of the built-in tiles, starting with a new bag.
+	#[must_use]
+	pub fn with_set(self, set: MinoSet) -> Self {
+		Self {
+			set,
+			bag: Vec::new(),
+			..self
+		}
+	}
+
+	/// The number of transforms of each tile put in the bag.
+	///
+	/// Every combination of flips and rotations is used, rather than each distinct orientation once,
+	/// so that every tile is dealt equally often.
+	fn bagged_transforms(&self) -> usize {
+		if self.bag_orientations {
+			NUM_TRANSFORMS
+		} else {
+			1
+		}
+	}
+
+	/// The number of minos in a full bag.
+	#[must_use]
+	pub fn bag_size(&self) -> usize {
+		self.copies * self.bagged_transforms() * self.set.len()
+	}
+
+	fn refill_bag(&mut self) {
+		let transforms = self.bagged_transforms();
+		for _ in 0..self.copies {
+			for &mino in self.set.minos() {
+				self.bag.extend(mino.transforms().take(transforms));
+			}
+		}
+		self.rng.shuffle(&mut self.bag);
+	}
+}
+
+impl MinoGenerator for BagGenerator {
+	fn next_mino(&mut self, _board: &Board) -> Mino {
+		if self.bag.is_empty() {
+			self.refill_bag();
+		}
+		let mino = self.bag.pop().unwrap_or_else(|| unreachable!());
+		if self.bag_orientations {
+			mino
+		} else {
+			random_orientation(mino, &mut self.rng)
+		}
+	}
+}
+
+/// Prefers minos that would be hard to place on the current board.
+///
+/// Every orientation of every tile is weighted by how few positions it can be placed at,
+/// raised to the power of the hardness.
+/// Minos that can't be placed at all are never picked unless nothing can be placed.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HardGenerator {
+	rng: Rng,
+	set: MinoSet,
+	#[cfg_attr(feature = "serde", serde(deserialize_with = "deserialize_hardness"))]
+	hardness: u32,
+}
+
+impl HardGenerator {
+	/// The largest supported hardness.
+	pub const MAX_HARDNESS: u32 = 6;
+
+	/// A hardness of zero picks tiles uniformly.
+	///
+	/// # Panics
+	///
+	/// Panics if `hardness` is greater than `MAX_HARDNESS`.
+	#[must_use]
+	pub fn new(seed: u64, hardness: u32) -> Self {
+		assert!(
+			hardness <= Self::MAX_HARDNESS,
+			"hardness must be at most {}",
+			Self::MAX_HARDNESS,
+		);
+		Self {
+			rng: Rng::new(seed),
+			set: MinoSet::builtin(),
+			hardness,
+		}
+	}
+
+	/// Pick from the tiles of `set` instead of the built-in tiles.
+	#[inline]
+	#[must_use]
+	pub fn with_set(self, set: MinoSet) -> Self {
+		Self { set, ..self }
+	}
+
+	#[inline]
+	#[must_use]
+	pub fn hardness(&self) -> u32 {
+		self.hardness
+	}
+}
+
+impl MinoGenerator for HardGenerator {
+	fn next_mino(&mut self, board: &Board) -> Mino {
+		// `(mino, number of orientations of its tile, number of placements)`
+		let mut candidates = Vec::new();
+		for &tile in self.set.minos() {
+			let orientations = tile.orientations();
+			let num_orientations = orientations.len();
+			candidates.extend(
+				orientations
+					.into_iter()
+					.map(|mino| (mino, num_orientations, board.count_legal_placements(mino))),
+			);
+		}
+
+		if candidates.iter().all(|&(_, _, placements)| placements == 0) {
+			let tile = random_tile(&self.set, &mut self.rng);
+			return random_orientation(tile, &mut self.rng);
+		}
+
+		// Integer weights keep the output identical across platforms.
+		// The scale is divisible by every possible number of orientations, so a hardness of zero gives each tile the same total weight.
+		let max_placements = candidates
+			.iter()
+			.map(|&(_, _, placements)| placements)
+			.max()
+			.unwrap_or_else(|| unreachable!());
+		// Each weight is at most `840 * 256^6`, which needs 58 bits, so the total can't overflow a `u128`.
+		let scale = 840
+			* u128::try_from(max_placements)
+				.unwrap_or_else(|_| unreachable!())
+				.pow(self.hardness);
+		let weights: Vec<u128> = candidates
+			.iter()
+			.map(|&(_, num_orientations, placements)| {
+				if placements == 0 {
+					0
+				} else {
+					let placements = u128::try_from(placements).unwrap_or_else(|_| unreachable!());
+					let num_orientations =
+						u128::try_from(num_orientations).unwrap_or_else(|_| unreachable!());
+					scale / placements.pow(self.hardness) / num_orientations
+				}
+			})
+			.collect();
+
+		// Large mino sets on large boards can make the total too big for `Rng::below_u64`.
+		// Scale the weights down until it fits then, keeping every placeable mino possible.
+		let shift = (u128::BITS - weights.iter().sum::<u128>().leading_zeros()).saturating_sub(63);
+		let weights: Vec<u64> = weights
+			.into_iter()
+			.map(|weight| {
+				let weight = if weight == 0 {
+					0
+				} else {
+					(weight >> shift).max(1)
+				};
+				u64::try_from(weight).unwrap_or_else(|_| unreachable!())
+			})
+			.collect();
+
+		let total: u64 = weights.iter().sum();
+		let mut pick = self.rng.below_u64(total);
+		for (&(mino, _, _), &weight) in candidates.iter().zip(&weights) {
+			if pick < weight {
+				return mino;
+			}
+			pick -= weight;
+		}
+		unreachable!()
+	}
+}
+
+/// Whether all of `minos` can be placed on `board` in some order, clearing filled features after each placement.
+fn is_survivable(board: &Board, minos: &[Mino]) -> bool {
+	let Some((&last, _)) = minos.split_last() else {
+		return true;
+	};
+	if minos.len() == 1 {
+		return board.can_place_anywhere(last);
+	}
+
+	(0..minos.len()).any(|index| {
+		// Skip identical minos, since they lead to identical searches.
+		if minos[..index].contains(&minos[index]) {
+			return false;
+		}
+		let mut rest = minos.to_vec();
+		let mino = rest.remove(index);
+		board.legal_placements(mino).any(|position| {
+			let mut board = *board;
+			board
+				.place_at(mino, position)
+				.unwrap_or_else(|_| unreachable!());
+			board.remove_filled();
+			is_survivable(&board, &rest)
+		})
+	})
+}
+
+/// Rerolls batches from the inner generator until all of the minos in the batch can be placed in some order.
+///
+/// If no such batch is found within the maximum number of rerolls, the last batch generated is dealt anyway,
+/// which `fallbacks` counts.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Survivable<G> {
+	inner: G,
+	max_rerolls: u32,
+	last_rerolls: u32,
+	total_rerolls: u64,
+	last_fell_back: bool,
+	fallbacks: u64,
+}
+
+impl<G> Survivable<G> {
+	/// The default maximum number of rerolls per batch.
+	pub const DEFAULT_MAX_REROLLS: u32 = 1000;
+
+	#[must_use]
+	pub fn new(inner: G) -> Self {
+		Self::with_max_rerolls(inner, Self::DEFAULT_MAX_REROLLS)
+	}
+
+	#[must_use]
+	pub fn with_max_rerolls(inner: G, max_rerolls: u32) -> Self {
+		Self {
+			inner,
+			max_rerolls,
+			last_rerolls: 0,
+			total_rerolls: 0,
+			last_fell_back: false,
+			fallbacks: 0,
+		}
+	}
+
+	#[inline]
+	#[must_use]
+	pub fn inner(&self) -> &G {
+		&self.inner
+	}
+
+	/// The number of batches that were rejected before the most recent batch was dealt.
+	#[inline]
+	#[must_use]
+	pub fn last_rerolls(&self) -> u32 {
+		self.last_rerolls
+	}
+
+	/// The number of batches that have been rejected in total.
+	#[inline]
+	#[must_use]
+	pub fn total_rerolls(&self) -> u64 {
+		self.total_rerolls
+	}
+
+	/// Whether the most recent batch was dealt without being survivable, because no survivable batch was found in time.
+	#[inline]
+	#[must_use]
+	pub fn last_fell_back(&self) -> bool {
+		self.last_fell_back
+	}
+
+	/// The number of batches that were dealt without being survivable.
+	#[inline]
+	#[must_use]
+	pub fn fallbacks(&self) -> u64 {
+		self.fallbacks
+	}
+}
+
+impl<G: MinoGenerator> MinoGenerator for Survivable<G> {
+	fn next_mino(&mut self, board: &Board) -> Mino {
+		self.inner.next_mino(board)
+	}
+
+	fn next_batch(&mut self, board: &Board) -> [Mino; BATCH_SIZE] {
+		self.last_rerolls = 0;
+		loop {
+			let batch = self.inner.next_batch(board);
+			if is_survivable(board, &batch) {
+				self.last_fell_back = false;
+				return batch;
+			}
+			if self.last_rerolls >= self.max_rerolls {
+				self.last_fell_back = true;
+				self.fallbacks += 1;
+				return batch;
+			}
+			self.last_rerolls += 1;
+			self.total_rerolls += 1;
+		}
+	}
+}
+
+/// One of the built-in generators, along with its settings.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(rename_all = "snake_case")
+)]
+pub enum GeneratorKind {
+	/// See `UniformGenerator`.
+	#[default]
+	Uniform,
+	/// See `BagGenerator`.
+	Bag {
+		#[cfg_attr(feature = "serde", serde(deserialize_with = "deserialize_copies"))]
+		copies: usize,
+		orientations: bool,
+	},
+	/// See `HardGenerator`.
+	Hard {
+		#[cfg_attr(feature = "serde", serde(deserialize_with = "deserialize_hardness"))]
+		hardness: u32,
+	},
+}
+
+#[cfg(feature = "serde")]
+fn deserialize_copies<'de, D: serde::Deserializer<'de>>(
+	deserializer: D,
+) -> Result<usize, D::Error> {
+	let copies = <usize as serde::Deserialize>::deserialize(deserializer)?;
+	if copies == 0 {
+		return Err(serde::de::Error::custom(
+			"the bag must contain at least one copy of each tile",
+		));
+	}
+	Ok(copies)
+}
+
+#[cfg(feature = "serde")]
+fn deserialize_hardness<'de, D: serde::Deserializer<'de>>(
+	deserializer: D,
+) -> Result<u32, D::Error> {
+	let hardness = <u32 as serde::Deserialize>::deserialize(deserializer)?;
+	if hardness > HardGenerator::MAX_HARDNESS {
+		return Err(serde::de::Error::custom(format_args!(
+			"hardness must be at most {}",
+			HardGenerator::MAX_HARDNESS,
+		)));
+	}
+	Ok(hardness)
+}
+
+/// A built-in generator chosen at runtime, for example from a command-line option.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(rename_all = "snake_case")
+)]
+pub enum AnyGenerator {
+	Uniform(UniformGenerator),
+	Bag(BagGenerator),
+	Hard(HardGenerator),
+	Survivable(Box<Survivable<AnyGenerator>>),
+}
+
+impl AnyGenerator {
+	/// # Panics
+	///
+	/// Panics if `kind` is `Bag` with zero `copies` or `Hard` with a hardness greater than `HardGenerator::MAX_HARDNESS`,
+	/// like `BagGenerator::new` and `HardGenerator::new`.
+	#[must_use]
+	pub fn new(kind: GeneratorKind, seed: u64) -> Self {
+		match kind {
+			GeneratorKind::Uniform => Self::Uniform(UniformGenerator::new(seed)),
+			GeneratorKind::Bag {
+				copies,
+				orientations,
+			} => Self::Bag(BagGenerator::new(seed, copies, orientations)),
+			GeneratorKind::Hard { hardness } => Self::Hard(HardGenerator::new(seed, hardness)),
+		}
+	}
+
+	/// Pick from the tiles of `set` instead of the built-in tiles.
+	#[must_use]
+	pub fn with_set(self, set: MinoSet) -> Self {
+		match self {
+			Self::Uniform(generator) => Self::Uniform(generator.with_set(set)),
+			Self::Bag(generator) => Self::Bag(generator.with_set(set)),
+			Self::Hard(generator) => Self::Hard(generator.with_set(set)),
+			Self::Survivable(mut generator) => {
+				generator.inner = generator.inner.with_set(set);
+				Self::Survivable(generator)
+			}
+		}
+	}
+
+	/// Wrap this generator in `Survivable`.
+	#[must_use]
+	pub fn survivable(self) -> Self {
+		Self::Survivable(Box::new(Survivable::new(self)))
+	}
+
+	/// The `Survivable` wrapper, for its reroll counts, if this generator has one.
+	#[must_use]
+	pub fn as_survivable(&self) -> Option<&Survivable<Self>> {
+		match self {
+			Self::Survivable(generator) => Some(generator),
+			_ => None,
+		}
+	}
+}
+
+impl MinoGenerator for AnyGenerator {
+	fn next_mino(&mut self, board: &Board) -> Mino {
+		match self {
+			Self::Uniform(generator) => generator.next_mino(board),
+			Self::Bag(generator) => generator.next_mino(board),
+			Self::Hard(generator) => generator.next_mino(board),
+			Self::Survivable(generator) => generator.next_mino(board),
+		}
+	}
+
+	fn next_batch(&mut self, board: &Board) -> [Mino; BATCH_SIZE] {
+		match self {
+			Self::Uniform(generator) => generator.next_batch(board),
+			Self::Bag(generator) => generator.next_batch(board),
+			Self::Hard(generator) => generator.next_batch(board),
+			Self::Survivable(generator) => generator.next_batch(board),
+		}
+	}
+}
+
+#[test]
+fn test_uniform_generator_is_deterministic() {
+	let board = Board::new();
+	let generate = |seed| {
+		let mut generator = UniformGenerator::new(seed);
+		(0..100)
+			.map(|_| generator.next_mino(&board))
+			.collect::<Vec<_>>()
+	};
+
+	assert_eq!(generate(42), generate(42));
+	assert_ne!(generate(42), generate(43));
+}
+
+#[test]
+fn test_uniform_generator_orientations() {
+	let board = Board::new();
+	let set: MinoSet = "[l]\n###\n#..".parse().unwrap();
+	let orientations = set.minos()[0].orientations();
+	let mut generator = UniformGenerator::new(3).with_set(set);
+	let mut counts = [0; NUM_TRANSFORMS];
+	for _ in 0..8000 {
+		let mino = generator.next_mino(&board);
+		counts[orientations
+			.iter()
+			.position(|&orientation| orientation == mino)
+			.unwrap()] += 1;
+	}
+	assert!(
+		counts.iter().all(|&count| (800..1200).contains(&count)),
+		"{counts:?}"
+	);
+}
+
+#[test]
+fn test_generators_use_set() {
+	let board = Board::new();
+	let set: MinoSet = "[bar-6]\n######".parse().unwrap();
+	let bar = set.minos()[0];
+	let is_bar = |mino: Mino| mino == bar || mino == bar.rotate_cw_90();
+	let mut generators = [
+		AnyGenerator::new(GeneratorKind::Uniform, 1),
+		AnyGenerator::new(
+			GeneratorKind::Bag {
+				copies: 1,
+				orientations: true,
+			},
+			1,
+		),
+		AnyGenerator::new(GeneratorKind::Hard { hardness: 2 }, 1).survivable(),
+	]
+	.map(|generator| generator.with_set(set.clone()));
+	for generator in &mut generators {
+		for _ in 0..10 {
+			assert!(is_bar(generator.next_mino(&board)));
+		}
+	}
+}
+
+#[test]
+fn test_bag_generator_deals_every_tile() {
+	let board = Board::new();
+	let tile_of = |mino: Mino| {
+		Mino::all()
+			.position(|tile| tile.transforms().any(|transformed| transformed == mino))
+			.unwrap()
+	};
+
+	for bag_orientations in [false, true] {
+		let mut generator = BagGenerator::new(7, 2, bag_orientations);
+		for _ in 0..3 {
+			let mut counts = vec![0; Mino::all().len()];
+			for _ in 0..generator.bag_size() {
+				counts[tile_of(generator.next_mino(&board))] += 1;
+			}
+			let expected = generator.bag_size() / Mino::all().len();
+			assert!(counts.iter().all(|&count| count == expected));
+		}
+	}
+}
+
+#[test]
+fn test_hard_generator_prefers_awkward_minos() {
+	// Leave only a single free column, so that only vertical pieces fit.
+	let mut board = Board::new();
+	for position in board.layout().positions() {
+		if position.x() != 4 {
+			board.set(position, true);
+		}
+	}
+
+	let mut generator = HardGenerator::new(3, 4);
+	for _ in 0..20 {
+		let mino = generator.next_mino(&board);
+		assert!(board.can_place_anywhere(mino));
+	}
+
+	// On an empty board, big minos have the fewest placements.
+	let board = Board::new();
+	let total_squares = |hardness| {
+		let mut generator = HardGenerator::new(3, hardness);
+		(0..200)
+			.map(|_| generator.next_mino(&board).num_squares())
+			.sum::<usize>()
+	};
+	assert!(total_squares(HardGenerator::MAX_HARDNESS) > total_squares(0) + 100);
+}
+
+#[test]
+fn test_hard_generator_large_weights() {
+	// Many tiles with a single placement on a big board give weights that don't add up in a `u64`.
+	let full = format!("{}\n", "#".repeat(16)).repeat(16);
+	let tiles: Vec<String> = (0..100)
+		.map(|index| format!("[full-{index}]\n{full}"))
+		.collect();
+	let board = Board::with_layout(Layout::new(16, 16, 4, 4).unwrap().intern());
+	let mut generator = HardGenerator::new(1, HardGenerator::MAX_HARDNESS).with_set(
+		format!("[single]\n#\n\n{}", tiles.join("\n"))
+			.parse()
+			.unwrap(),
+	);
+	let minos: Vec<Mino> = (0..20).map(|_| generator.next_mino(&board)).collect();
+	assert!(minos.iter().any(|mino| mino.num_squares() == 256));
+}
+
+#[test]
+fn test_survivable_rerolls() {
+	// Leave room for exactly one 1x1 mino, whose placement clears the whole board.
+	let mut board = Board::new();
+	for position in board.layout().positions().skip(1) {
+		board.set(position, true);
+	}
+	let single = Mino::all().find(|mino| mino.num_squares() == 1).unwrap();
+	let bar = Mino::all().find(|mino| mino.num_squares() == 5).unwrap();
+	assert!(is_survivable(&board, &[single, single]));
+	assert!(is_survivable(&board, &[bar, bar, single]));
+	assert!(!is_survivable(&board, &[bar, bar, bar]));
+
+	let mut generator =
+		Survivable::with_max_rerolls(RepeatGenerator(vec![bar, bar, bar, single]), 10);
+	// The first batch, `[bar, bar, bar]`, is rejected.
+	assert_eq!(generator.next_batch(&board), [single, bar, bar]);
+	assert_eq!(generator.last_rerolls(), 1);
+	assert_eq!(generator.total_rerolls(), 1);
+	assert!(!generator.last_fell_back());
+
+	// Only unsurvivable batches can be dealt, so the last one is dealt anyway.
+	let mut generator = Survivable::with_max_rerolls(RepeatGenerator(vec![bar]), 2);
+	assert_eq!(generator.next_batch(&board), [bar, bar, bar]);
+	assert_eq!(generator.last_rerolls(), 2);
+	assert!(generator.last_fell_back());
+	assert_eq!(generator.fallbacks(), 1);
+}
+
+/// Deals the given minos in a loop.
+#[cfg(test)]
+#[derive(Clone)]
+pub(crate) struct RepeatGenerator(pub Vec<Mino>);
+
+#[cfg(test)]
+impl MinoGenerator for RepeatGenerator {
+	fn next_mino(&mut self, _board: &Board) -> Mino {
+		let mino = self.0.remove(0);
+		self.0.push(mino);
+		mino
+	}
+}
@@ -0,0 +1,620 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use crate::bits::Bits;
+use crate::board::{Position, SquareIndex, WouldRemove};
+use crate::util::Coordinate;
+
+/// The largest supported length of an edge of the board.
+pub const MAX_BOARD_SIZE: Coordinate = 16;
+pub(crate) const MAX_CELLS: usize = MAX_BOARD_SIZE as usize * MAX_BOARD_SIZE as usize;
+/// Every row and column, plus at most one square per cell.
+const MAX_REGIONS: usize = 2 * MAX_BOARD_SIZE as usize + MAX_CELLS;
+
+/// The shape of a board: its dimensions and how it is divided into squares.
+///
+/// Squares are usually rectangular boxes, but they can be any connected regions, like in jigsaw sudoku.
+/// A layout can be parsed from a region map: one line per row, with a character per cell naming its region.
+/// `Display` writes one.
+///
+/// Boards refer to their layout by a `&'static` reference so that they can stay `Copy`.
+/// Use `Layout::intern` to get one for a custom layout.
+///
+/// With the `serde` feature, layouts are serialized as `{ "regions": [[0, 0, 0, 1, ...], ...] }`,
+/// with an array of square indices for each row.
+#[allow(missing_copy_implementations /* too large to copy implicitly */)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(into = "LayoutRepr", try_from = "LayoutRepr")
+)]
+pub struct Layout {
+	width: Coordinate,
+	height: Coordinate,
+	/// The square that each cell belongs to, row-major with a stride of `width`.
+	squares: [u8; MAX_CELLS],
+	num_squares: u16,
+	/// The cells of each region that can be cleared: the rows, then the columns, then the squares.
+	masks: [Bits; MAX_REGIONS],
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct LayoutRepr {
+	regions: Vec<Vec<u8>>,
+}
+
+#[cfg(feature = "serde")]
+impl From<Layout> for LayoutRepr {
+	fn from(layout: Layout) -> Self {
+		let width = usize::try_from(layout.width).unwrap_or_else(|_| unreachable!());
+		Self {
+			regions: layout.squares[..layout.num_cells()]
+				.chunks(width)
+				.map(<[u8]>::to_vec)
+				.collect(),
+		}
+	}
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<LayoutRepr> for Layout {
+	type Error = LayoutError;
+
+	fn try_from(repr: LayoutRepr) -> Result<Self, Self::Error> {
+		let width = repr.regions.first().map_or(0, Vec::len);
+		if repr.regions.iter().any(|row| row.len() != width) {
+			return Err(LayoutError::RaggedRows);
+		}
+		let size = |len: usize| Coordinate::try_from(len).map_err(|_| LayoutError::InvalidSize);
+		Self::from_regions(
+			size(width)?,
+			size(repr.regions.len())?,
+			&repr.regions.concat(),
+		)
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutError {
+	/// The width or height is zero or greater than `MAX_BOARD_SIZE`.
+	InvalidSize,
+	/// The board cannot be evenly divided into boxes of the given size.
+	UnevenBoxes,
+	/// The region map doesn't have exactly one region per cell.
+	WrongRegionCount,
+	/// The rows of the region map have different lengths.
+	RaggedRows,
+	/// A region is split into several parts.
+	DisconnectedRegion,
+}
+
+impl Display for LayoutError {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::InvalidSize => write!(
+				formatter,
+				"the width and height must be between 1 and {MAX_BOARD_SIZE}"
+			),
+			Self::UnevenBoxes => formatter.write_str("the boxes must evenly divide the board"),
+			Self::WrongRegionCount => formatter.write_str("there must be one region per cell"),
+			Self::RaggedRows => formatter.write_str("all rows must have the same length"),
+			Self::DisconnectedRegion => formatter.write_str("every region must be connected"),
+		}
+	}
+}
+
+impl std::error::Error for LayoutError {}
+
+static STANDARD: Layout = Layout::boxes_unchecked(9, 9, 3, 3);
+
+static INTERNED: Mutex<Vec<&'static Layout>> = Mutex::new(Vec::new());
+
+/// Region maps of the built-in layouts other than `standard`, by name.
+const BUILTIN: &[(&str, &str)] = &[
+	(
+		"jigsaw-1",
+		"\
+111112333
+122222333
+111224333
+555524666
+544444666
+557448869
+577778969
+577888969
+778889999",
+	),
+	(
+		"jigsaw-2",
+		"\
+111222223
+441523333
+441523363
+741522663
+741555688
+741595688
+741596688
+749999668
+777799988",
+	),
+	(
+		"jigsaw-3",
+		"\
+111222222
+111233333
+144253533
+114255536
+444556666
+474586699
+477588669
+777789999
+778888899",
+	),
+];
+
+impl Layout {
+	/// The classic 9x9 board with 3x3 squares.
+	#[inline]
+	#[must_use]
+	pub fn standard() -> &'static Self {
+		&STANDARD
+	}
+
+	/// A `width` by `height` board divided into `box_width` by `box_height` squares.
+	///
+	/// # Errors
+	///
+	/// Fails if the dimensions are out of range or the boxes don't evenly divide the board.
+	pub fn new(
+		width: Coordinate,
+		height: Coordinate,
+		box_width: Coordinate,
+		box_height: Coordinate,
+	) -> Result<Self, LayoutError> {
+		let valid_size = 1..=MAX_BOARD_SIZE;
+		if !valid_size.contains(&width) || !valid_size.contains(&height) {
+			return Err(LayoutError::InvalidSize);
+		}
+		if box_width <= 0 || box_height <= 0 || width % box_width != 0 || height % box_height != 0 {
+			return Err(LayoutError::UnevenBoxes);
+		}
+		Ok(Self::boxes_unchecked(width, height, box_width, box_height))
+	}
+
+	#[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation /* checked by callers */)]
+	const fn boxes_unchecked(
+		width: Coordinate,
+		height: Coordinate,
+		box_width: Coordinate,
+		box_height: Coordinate,
+	) -> Self {
+		let boxes_per_row = width / box_width;
+
+		let mut squares = [0; MAX_CELLS];
+		let mut y = 0;
+		while y < height {
+			let mut x = 0;
+			while x < width {
+				squares[y as usize * width as usize + x as usize] =
+					((y / box_height) as usize * boxes_per_row as usize + (x / box_width) as usize) as u8;
+				x += 1;
+			}
+			y += 1;
+		}
+
+		Self {
+			width,
+			height,
+			squares,
+			num_squares: (boxes_per_row as u16) * ((height / box_height) as u16),
+			masks: [Bits::ZERO; MAX_REGIONS],
+		}
+		.with_masks()
+	}
+
+	/// Fill in `masks` from the dimensions and `squares`.
+	#[allow(clippy::cast_sign_loss /* dimensions are positive */)]
+	const fn with_masks(mut self) -> Self {
+		let width = self.width as usize;
+		let height = self.height as usize;
+		let mut y = 0;
+		while y < height {
+			let mut x = 0;
+			while x < width {
+				let cell = y * width + x;
+				let square = self.squares[cell] as usize;
+				self.masks[y] = self.masks[y].with(cell);
+				self.masks[height + x] = self.masks[height + x].with(cell);
+				self.masks[height + width + square] = self.masks[height + width + square].with(cell);
+				x += 1;
+			}
+			y += 1;
+		}
+		self
+	}
+
+	/// A layout whose squares are the arbitrary regions given by `regions`,
+	/// which holds the region of each cell in row-major order.
+	///
+	/// Regions can be labeled with any values; they are numbered in order of first appearance.
+	///
+	/// # Errors
+	///
+	/// Fails if the dimensions are out of range, `regions` has the wrong length, or a region isn't connected.
+	pub fn from_regions(
+		width: Coordinate,
+		height: Coordinate,
+		regions: &[u8],
+	) -> Result<Self, LayoutError> {
+		let valid_size = 1..=MAX_BOARD_SIZE;
+		if !valid_size.contains(&width) || !valid_size.contains(&height) {
+			return Err(LayoutError::InvalidSize);
+		}
+
+		let mut ret = Self {
+			width,
+			height,
+			squares: [0; MAX_CELLS],
+			num_squares: 0,
+			masks: [Bits::ZERO; MAX_REGIONS],
+		};
+		if regions.len() != ret.num_cells() {
+			return Err(LayoutError::WrongRegionCount);
+		}
+
+		let mut labels = Vec::new();
+		for (square, label) in ret.squares.iter_mut().zip(regions) {
+			let index = labels
+				.iter()
+				.position(|existing| existing == label)
+				.unwrap_or_else(|| {
+					labels.push(*label);
+					labels.len() - 1
+				});
+			*square = u8::try_from(index).unwrap_or_else(|_| unreachable!());
+		}
+		ret.num_squares = u16::try_from(labels.len()).unwrap_or_else(|_| unreachable!());
+
+		if ret.squares().any(|square| !ret.is_connected(square)) {
+			return Err(LayoutError::DisconnectedRegion);
+		}
+
+		Ok(ret.with_masks())
+	}
+
+	fn is_connected(&self, square: SquareIndex) -> bool {
+		let Some(start) = self.square(square).next() else {
+			return true;
+		};
+
+		let mut seen = vec![false; self.num_cells()];
+		seen[self.cell_index(start)] = true;
+		let mut stack = vec![start];
+		let mut reached = 1;
+		while let Some(position) = stack.pop() {
+			let (x, y) = position.to_xy();
+			for (nx, ny) in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+				let Some(neighbor) = self.position(nx, ny) else {
+					continue;
+				};
+				let index = self.cell_index(neighbor);
+				if !seen[index] && self.square_of(neighbor) == square {
+					seen[index] = true;
+					reached += 1;
+					stack.push(neighbor);
+				}
+			}
+		}
+
+		reached == self.square(square).count()
+	}
+
+	/// Look up a built-in layout: `standard` or one of the jigsaw layouts `jigsaw-1` through `jigsaw-3`.
+	#[must_use]
+	pub fn builtin(name: &str) -> Option<&'static Self> {
+		if name == "standard" {
+			return Some(Self::standard());
+		}
+		BUILTIN
+			.iter()
+			.find(|(builtin, _)| *builtin == name)
+			.map(|(_, map)| {
+				map
+					.parse::<Self>()
+					.unwrap_or_else(|_| unreachable!())
+					.intern()
+			})
+	}
+
+	/// The names accepted by `Layout::builtin`.
+	pub fn builtin_names() -> impl Iterator<Item = &'static str> {
+		std::iter::once("standard").chain(BUILTIN.iter().map(|(name, _)| *name))
+	}
+
+	/// Get a `'static` reference to an equal layout, allocating one if no equal layout has been interned before.
+	///
+	/// # Panics
+	///
+	/// Panics if another thread panicked while interning a layout.
+	#[must_use]
+	pub fn intern(self) -> &'static Self {
+		if self == STANDARD {
+			return &STANDARD;
+		}
+
+		let mut interned = INTERNED.lock().unwrap();
+		if let Some(&existing) = interned.iter().find(|&&existing| *existing == self) {
+			return existing;
+		}
+		let ret = Box::leak(Box::new(self));
+		interned.push(ret);
+		ret
+	}
+
+	#[inline]
+	#[must_use]
+	pub fn width(&self) -> Coordinate {
+		self.width
+	}
+
+	#[inline]
+	#[must_use]
+	pub fn height(&self) -> Coordinate {
+		self.height
+	}
+
+	#[inline]
+	#[must_use]
+	pub fn num_cells(&self) -> usize {
+		self.cell_index_unchecked(0, self.height)
+	}
+
+	/// The number of squares (boxes) the board is divided into.
+	#[inline]
+	#[must_use]
+	pub fn num_squares(&self) -> usize {
+		usize::from(self.num_squares)
+	}
+
+	#[inline]
+	#[must_use]
+	pub fn contains(&self, position: Position) -> bool {
+		position.x() < self.width && position.y() < self.height
+	}
+
+	/// Returns `None` if `x` or `y` are outside of the board.
+	#[inline]
+	#[must_use]
+	pub fn position(&self, x: Coordinate, y: Coordinate) -> Option<Position> {
+		if (0..self.width).contains(&x) && (0..self.height).contains(&y) {
+			Some(Position::new_unchecked(x, y))
+		} else {
+			None
+		}
+	}
+
+	/// All positions on the board, in row-major order.
+	#[must_use]
+	pub fn positions(&self) -> impl ExactSizeIterator<Item = Position> + Clone {
+		let width = usize::try_from(self.width).unwrap_or_else(|_| unreachable!());
+		(0..self.num_cells()).map(move |index| {
+			let coordinate =
+				|value: usize| Coordinate::try_from(value).unwrap_or_else(|_| unreachable!());
+			Position::new_unchecked(coordinate(index % width), coordinate(index / width))
+		})
+	}
+
+	fn cell_index_unchecked(&self, x: Coordinate, y: Coordinate) -> usize {
+		let usize = |value: Coordinate| usize::try_from(value).unwrap_or_else(|_| unreachable!());
+		usize(y) * usize(self.width) + usize(x)
+	}
+
+	/// The index of the cell at `position` in row-major order.
+	///
+	/// # Panics
+	///
+	/// Panics if `position` is outside of the board.
+	#[inline]
+	#[must_use]
+	pub(crate) fn cell_index(&self, position: Position) -> usize {
+		assert!(self.contains(position), "position is outside of the board");
+		self.cell_index_unchecked(position.x(), position.y())
+	}
+
+	/// The square that `position` belongs to.
+	///
+	/// # Panics
+	///
+	/// Panics if `position` is outside of the board.
+	#[inline]
+	#[must_use]
+	pub fn square_of(&self, position: Position) -> SquareIndex {
+		SquareIndex::new(self.squares[self.cell_index(position)])
+	}
+
+	/// All squares of the board.
+	#[must_use]
+	pub fn squares(&self) -> impl ExactSizeIterator<Item = SquareIndex> + Clone {
+		(0..self.num_squares)
+			.map(|index| SquareIndex::new(u8::try_from(index).unwrap_or_else(|_| unreachable!())))
+	}
+
+	/// All positions within the square with the given index.
+	pub fn square(&self, index: SquareIndex) -> impl Iterator<Item = Position> + Clone + '_ {
+		self
+			.positions()
+			.filter(move |&position| self.square_of(position) == index)
+	}
+
+	/// The number of regions that can be cleared: rows, columns and squares.
+	#[inline]
+	#[must_use]
+	pub fn num_regions(&self) -> usize {
+		self.num_rows_and_columns() + self.num_squares()
+	}
+
+	fn num_rows_and_columns(&self) -> usize {
+		let usize = |value: Coordinate| usize::try_from(value).unwrap_or_else(|_| unreachable!());
+		usize(self.height) + usize(self.width)
+	}
+
+	/// Every region that can be cleared: all rows, then all columns, then all squares.
+	#[must_use]
+	pub fn regions(&self) -> impl ExactSizeIterator<Item = WouldRemove> + Clone + '_ {
+		let height = usize::try_from(self.height).unwrap_or_else(|_| unreachable!());
+		let coordinate = |value: usize| Coordinate::try_from(value).unwrap_or_else(|_| unreachable!());
+		(0..self.num_regions()).map(move |index| {
+			if index < height {
+				WouldRemove::Horizontal {
+					y: coordinate(index),
+				}
+			} else if index < self.num_rows_and_columns() {
+				WouldRemove::Vertical {
+					x: coordinate(index - height),
+				}
+			} else {
+				let square = index - self.num_rows_and_columns();
+				WouldRemove::Square {
+					index: SquareIndex::new(u8::try_from(square).unwrap_or_else(|_| unreachable!())),
+				}
+			}
+		})
+	}
+
+	/// Every region that can be cleared along with the cells in it.
+	pub(crate) fn region_masks(&self) -> impl Iterator<Item = (WouldRemove, Bits)> + Clone + '_ {
+		self.regions().zip(self.masks.iter().copied())
+	}
+}
+
+/// The characters that `Display` labels the first squares with. Later squares get letters from Latin Extended-A and -B.
+const REGION_LABELS: &str = "123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0";
+
+fn region_label(square: SquareIndex) -> char {
+	let index = square.get();
+	REGION_LABELS.chars().nth(index).unwrap_or_else(|| {
+		let extended = u32::try_from(index - REGION_LABELS.len()).unwrap_or_else(|_| unreachable!());
+		char::from_u32(0x100 + extended).unwrap_or_else(|| unreachable!())
+	})
+}
+
+/// A region map in the format accepted by `FromStr`, labeling squares `1` through `9`, then `a`, and so on.
+impl Display for Layout {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		for position in self.positions() {
+			if position.x() == 0 && position.y() > 0 {
+				formatter.write_str("\n")?;
+			}
+			write!(formatter, "{}", region_label(self.square_of(position)))?;
+		}
+		Ok(())
+	}
+}
+
+impl FromStr for Layout {
+	type Err = LayoutError;
+
+	/// Parse a region map. Leading and trailing whitespace on each line is ignored, as are blank lines.
+	fn from_str(map: &str) -> Result<Self, Self::Err> {
+		let rows: Vec<&str> = map
+			.lines()
+			.map(str::trim)
+			.filter(|row| !row.is_empty())
+			.collect();
+		let width = rows.first().map_or(0, |row| row.chars().count());
+		if rows.iter().any(|row| row.chars().count() != width) {
+			return Err(LayoutError::RaggedRows);
+		}
+		let size = |len: usize| Coordinate::try_from(len).map_err(|_| LayoutError::InvalidSize);
+		let width = size(width)?;
+		let height = size(rows.len())?;
+
+		let mut labels = Vec::new();
+		let mut regions = Vec::new();
+		for label in rows.iter().flat_map(|row| row.chars()) {
+			let index = labels
+				.iter()
+				.position(|&existing| existing == label)
+				.unwrap_or_else(|| {
+					labels.push(label);
+					labels.len() - 1
+				});
+			regions.push(u8::try_from(index).map_err(|_| LayoutError::WrongRegionCount)?);
+		}
+
+		Self::from_regions(width, height, &regions)
+	}
+}
+
+#[test]
+fn test_layout_boxes() {
+	let layout = Layout::new(6, 6, 3, 2).unwrap();
+	assert_eq!(layout.num_cells(), 36);
+	assert_eq!(layout.num_squares(), 6);
+	for square in layout.squares() {
+		assert_eq!(layout.square(square).count(), 6);
+	}
+	assert_eq!(
+		layout
+			.square(layout.square_of(Position::new(4, 3).unwrap()))
+			.map(Position::to_xy)
+			.collect::<Vec<_>>(),
+		[(3, 2), (4, 2), (5, 2), (3, 3), (4, 3), (5, 3)],
+	);
+
+	assert_eq!(Layout::new(0, 9, 3, 3), Err(LayoutError::InvalidSize));
+	assert_eq!(Layout::new(17, 9, 1, 3), Err(LayoutError::InvalidSize));
+	assert_eq!(Layout::new(9, 9, 2, 3), Err(LayoutError::UnevenBoxes));
+
+	assert!(std::ptr::eq(
+		Layout::new(9, 9, 3, 3).unwrap().intern(),
+		Layout::standard()
+	));
+	let interned = Layout::new(12, 12, 4, 4).unwrap().intern();
+	assert!(std::ptr::eq(
+		Layout::new(12, 12, 4, 4).unwrap().intern(),
+		interned
+	));
+}
+
+#[test]
+fn test_layout_regions() {
+	for name in Layout::builtin_names() {
+		let layout = Layout::builtin(name).unwrap();
+		assert_eq!(layout.num_squares(), 9, "{name}");
+		for square in layout.squares() {
+			assert_eq!(layout.square(square).count(), 9, "{name}");
+		}
+	}
+	assert!(Layout::builtin("nonexistent").is_none());
+
+	let layout: Layout = "aab\nacb\nccb".parse().unwrap();
+	assert_eq!(layout.num_squares(), 3);
+	assert_eq!(
+		layout.square_of(Position::new(1, 1).unwrap()),
+		layout.square_of(Position::new(0, 2).unwrap()),
+	);
+
+	assert_eq!("ab\nabc".parse::<Layout>(), Err(LayoutError::RaggedRows));
+	assert_eq!(
+		"aba\nbbb".parse::<Layout>(),
+		Err(LayoutError::DisconnectedRegion)
+	);
+	assert_eq!(
+		Layout::from_regions(2, 2, &[0, 1, 2]),
+		Err(LayoutError::WrongRegionCount)
+	);
+
+	assert!(Layout::standard()
+		.to_string()
+		.starts_with("111222333\n111222333\n"));
+	for layout in Layout::builtin_names()
+		.map(|name| Layout::builtin(name).unwrap().clone())
+		.chain([Layout::new(16, 16, 1, 1).unwrap()])
+	{
+		assert_eq!(layout.to_string().parse(), Ok(layout));
+	}
+}
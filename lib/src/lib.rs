@@ -8,18 +8,51 @@
 	non_ascii_idents,
 	nonstandard_style,
 	noop_method_call,
-	pointer_structural_match,
-	private_in_public,
 	rust_2018_idioms,
 	unused_qualifications
 )]
 #![warn(clippy::pedantic)]
 #![forbid(unsafe_code)]
 
-pub use crate::board::{Board, Position};
-pub use crate::mino::Mino;
+pub use crate::board::{
+	Board, BoardParseError, Cleared, OutOfBounds, PlaceError, Position, SquareIndex, WouldRemove,
+};
+pub use crate::game::{Game, PlacementOutcome, PlayError, Selection, BATCH_SIZE};
+pub use crate::generator::{
+	AnyGenerator, BagGenerator, GeneratorKind, HardGenerator, MinoGenerator, Survivable,
+	UniformGenerator,
+};
+pub use crate::layout::{Layout, LayoutError, MAX_BOARD_SIZE};
+pub use crate::mino::{Mino, MinoParseError, MAX_MINO_SIZE};
+pub use crate::mino_kind::{MinoKind, MinoKindParseError, Orientation};
+pub use crate::mino_set::{MinoSet, MinoSetParseError};
+pub use crate::replay::{
+	Replay, ReplayError, ReplayMove, ReplayParseError, Verification, REPLAY_VERSION,
+};
+pub use crate::rng::Rng;
+pub use crate::rule_args::{RuleArgs, RuleArgsError};
+pub use crate::rules::Rules;
+pub use crate::scoring::{
+	ClassicScoring, ScoringKind, ScoringKindParseError, ScoringRule, TenTenScoring, WoodokuScoring,
+};
+pub use crate::strategy::{
+	AnyStrategy, GreedyStrategy, HeuristicStrategy, RandomStrategy, Strategy, StrategyKind,
+	StrategyKindParseError, Turn,
+};
 pub use crate::util::Coordinate;
 
+mod bits;
 mod board;
+mod game;
+mod generator;
+mod layout;
 mod mino;
+mod mino_kind;
+mod mino_set;
+mod replay;
+mod rng;
+mod rule_args;
+mod rules;
+mod scoring;
+mod strategy;
 mod util;
@@ -1,119 +1,332 @@
-use std::fmt::{self, Debug, Formatter};
+use std::fmt::{self, Debug, Display, Formatter};
+use std::str::FromStr;
 
-use crate::util::{grid_fmt, min_bytes_for_bits, Coordinate};
+use crate::mino_kind::{MinoKind, Orientation};
+use crate::util::{grid_fmt, Coordinate};
 
-pub const MINO_SIZE: i8 = 5;
-const NUM_SQUARES_IN_MINO: i8 = MINO_SIZE * MINO_SIZE;
+/// The largest supported width and height of a mino's bounding box.
+pub const MAX_MINO_SIZE: Coordinate = 16;
 
+/// A shape within a bounding box of up to `MAX_MINO_SIZE` by `MAX_MINO_SIZE` squares.
+///
+/// The bounding box doesn't have to be tight: the built-in tiles all use a 5x5 box so that they are centered when transformed.
+///
+/// With the `serde` feature, minos are serialized as an array of rows in the format of `Display`.
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(into = "MinoRows", try_from = "MinoRows")
+)]
 pub struct Mino {
-	bits: [u8; min_bytes_for_bits(NUM_SQUARES_IN_MINO as usize)],
+	width: u8,
+	height: u8,
+	/// Bit `x` of `rows[y]` is set if the square at `(x, y)` is filled.
+	/// Invariant: bits outside of the bounding box are zero.
+	rows: [u16; MAX_MINO_SIZE as usize],
 }
 
 impl Debug for Mino {
 	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
-		grid_fmt(formatter, "Mino", MINO_SIZE as _, MINO_SIZE as _, |x, y| {
-			if self
-				.at(
-					x.try_into().unwrap_or_else(|_| unreachable!()),
-					y.try_into().unwrap_or_else(|_| unreachable!()),
-				)
-				.unwrap_or_else(|| unreachable!())
-			{
-				'1'
-			} else {
-				'0'
+		grid_fmt(
+			formatter,
+			"Mino",
+			self.height.into(),
+			self.width.into(),
+			|x, y| {
+				if self
+					.at(
+						x.try_into().unwrap_or_else(|_| unreachable!()),
+						y.try_into().unwrap_or_else(|_| unreachable!()),
+					)
+					.unwrap_or_else(|| unreachable!())
+				{
+					'1'
+				} else {
+					'0'
+				}
+			},
+		)
+	}
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+struct MinoRows(Vec<String>);
+
+#[cfg(feature = "serde")]
+impl From<Mino> for MinoRows {
+	fn from(mino: Mino) -> Self {
+		Self(mino.to_string().lines().map(str::to_owned).collect())
+	}
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<MinoRows> for Mino {
+	type Error = MinoParseError;
+
+	fn try_from(MinoRows(rows): MinoRows) -> Result<Self, Self::Error> {
+		rows.join("\n").parse()
+	}
+}
+
+/// One line per row of the bounding box, with `#` for filled squares and `.` for empty ones.
+impl Display for Mino {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		for y in 0..self.height() {
+			if y > 0 {
+				formatter.write_str("\n")?;
 			}
-		})
+			for x in 0..self.width() {
+				let filled = self.at(x, y).unwrap_or_else(|| unreachable!());
+				formatter.write_str(if filled { "#" } else { "." })?;
+			}
+		}
+		Ok(())
 	}
 }
 
-macro_rules! make_mino {
-	(
-		$x00:expr, $x10:expr, $x20:expr, $x30:expr, $x40:expr;
-		$x01:expr, $x11:expr, $x21:expr, $x31:expr, $x41:expr;
-		$x02:expr, $x12:expr, $x22:expr, $x32:expr, $x42:expr;
-		$x03:expr, $x13:expr, $x23:expr, $x33:expr, $x43:expr;
-		$x04:expr, $x14:expr, $x24:expr, $x34:expr, $x44:expr;
-	) => {
-		Mino {
-			bits: [
-				($x00 as u8) << 0
-					| ($x10 as u8) << 1
-					| ($x20 as u8) << 2
-					| ($x30 as u8) << 3
-					| ($x40 as u8) << 4
-					| ($x01 as u8) << 5
-					| ($x11 as u8) << 6
-					| ($x21 as u8) << 7,
-				($x31 as u8) << 0
-					| ($x41 as u8) << 1
-					| ($x02 as u8) << 2
-					| ($x12 as u8) << 3
-					| ($x22 as u8) << 4
-					| ($x32 as u8) << 5
-					| ($x42 as u8) << 6
-					| ($x03 as u8) << 7,
-				($x13 as u8) << 0
-					| ($x23 as u8) << 1
-					| ($x33 as u8) << 2
-					| ($x43 as u8) << 3
-					| ($x04 as u8) << 4
-					| ($x14 as u8) << 5
-					| ($x24 as u8) << 6
-					| ($x34 as u8) << 7,
-				($x44 as u8) << 0,
-			],
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MinoParseError {
+	/// There are no rows.
+	Empty,
+	/// None of the squares are filled.
+	NoSquares,
+	/// The bounding box is larger than `MAX_MINO_SIZE` in either direction.
+	TooLarge,
+	/// A row has a different length than the first row. Rows are numbered from 1.
+	RaggedRow { row: usize },
+	/// A character other than `#` or `.` was found. Rows and columns are numbered from 1.
+	InvalidChar {
+		row: usize,
+		column: usize,
+		char: char,
+	},
+}
+
+impl Display for MinoParseError {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Empty => formatter.write_str("the shape has no rows"),
+			Self::NoSquares => formatter.write_str("the shape has no filled squares (`#`)"),
+			Self::TooLarge => write!(
+				formatter,
+				"the shape is larger than {MAX_MINO_SIZE}x{MAX_MINO_SIZE}"
+			),
+			Self::RaggedRow { row } => write!(
+				formatter,
+				"row {row} has a different length than the first row"
+			),
+			Self::InvalidChar { row, column, char } => write!(
+				formatter,
+				"invalid character {char:?} at row {row}, column {column}; expected `#` or `.`"
+			),
 		}
+	}
+}
+
+impl std::error::Error for MinoParseError {}
+
+impl FromStr for Mino {
+	type Err = MinoParseError;
+
+	/// Parse the format produced by `Display`. Leading and trailing whitespace on each line is ignored, as are blank lines.
+	fn from_str(shape: &str) -> Result<Self, Self::Err> {
+		let rows: Vec<&str> = shape
+			.lines()
+			.map(str::trim)
+			.filter(|row| !row.is_empty())
+			.collect();
+		let Some(first) = rows.first() else {
+			return Err(MinoParseError::Empty);
+		};
+		let width = first.chars().count();
+		let size = |len: usize| {
+			Coordinate::try_from(len)
+				.ok()
+				.filter(|&len| len <= MAX_MINO_SIZE)
+				.ok_or(MinoParseError::TooLarge)
+		};
+		let (width, height) = (size(width)?, size(rows.len())?);
+
+		let mut grid = [[false; MAX_MINO_SIZE as usize]; MAX_MINO_SIZE as usize];
+		for ((y, row), grid_row) in rows.iter().enumerate().zip(&mut grid) {
+			if row.chars().count() != first.chars().count() {
+				return Err(MinoParseError::RaggedRow { row: y + 1 });
+			}
+			for ((x, char), square) in row.chars().enumerate().zip(grid_row) {
+				*square = match char {
+					'#' => true,
+					'.' => false,
+					_ => {
+						return Err(MinoParseError::InvalidChar {
+							row: y + 1,
+							column: x + 1,
+							char,
+						})
+					}
+				};
+			}
+		}
+
+		let ret = Self::from_fn(width, height, |x, y| {
+			grid[usize::try_from(y).unwrap_or_else(|_| unreachable!())]
+				[usize::try_from(x).unwrap_or_else(|_| unreachable!())]
+		});
+		if ret.num_squares() == 0 {
+			return Err(MinoParseError::NoSquares);
+		}
+		Ok(ret)
+	}
+}
+
+/// Rows of cells separated by semicolons, where nonzero cells are filled.
+macro_rules! make_mino {
+	($($($cell:expr),+;)+) => {
+		Mino::from_grid(&[$(&[$($cell != 0),+]),+])
 	};
 }
 
 impl Mino {
+	/// The built-in tiles. Use `MinoSet` to play with a different set.
 	#[inline]
 	#[must_use]
 	pub fn all() -> impl ExactSizeIterator<Item = Self> {
-		TILES.iter().copied()
+		TILES.iter().map(|&(_, mino)| mino)
+	}
+
+	/// The built-in tiles, as returned by `Mino::all`, along with their names.
+	pub(crate) fn named() -> impl ExactSizeIterator<Item = (&'static str, Self)> {
+		TILES.iter().map(|&(kind, mino)| (kind.name(), mino))
+	}
+
+	/// Which built-in tile this is and how it was flipped and rotated, or `None` if it isn't one of the built-in tiles.
+	///
+	/// If the mino is exactly a transformed tile, the first orientation in `Orientation::ALL` that produces it is returned.
+	/// Otherwise, shapes are compared regardless of where they are within the bounding box.
+	#[must_use]
+	pub fn identify(self) -> Option<(MinoKind, Orientation)> {
+		let candidates = || {
+			MinoKind::ALL.into_iter().flat_map(|kind| {
+				Orientation::ALL
+					.into_iter()
+					.map(move |orientation| (kind, orientation, orientation.apply(kind.mino())))
+			})
+		};
+		let shape = self.trim();
+		candidates()
+			.find(|&(_, _, mino)| mino == self)
+			.or_else(|| candidates().find(|&(_, _, mino)| mino.trim() == shape))
+			.map(|(kind, orientation, _)| (kind, orientation))
+	}
+
+	/// Panics if the grid is empty, too large, or not rectangular.
+	#[allow(clippy::cast_possible_truncation /* checked by the assertions */)]
+	const fn from_grid(grid: &[&[bool]]) -> Self {
+		let height = grid.len();
+		assert!(height > 0 && height <= MAX_MINO_SIZE as usize);
+		let width = grid[0].len();
+		assert!(width > 0 && width <= MAX_MINO_SIZE as usize);
+
+		let mut rows = [0; MAX_MINO_SIZE as usize];
+		let mut y = 0;
+		while y < height {
+			assert!(grid[y].len() == width, "rows must have the same length");
+			let mut x = 0;
+			while x < width {
+				if grid[y][x] {
+					rows[y] |= 1 << x;
+				}
+				x += 1;
+			}
+			y += 1;
+		}
+
+		Self {
+			width: width as u8,
+			height: height as u8,
+			rows,
+		}
+	}
+
+	/// The width of the bounding box.
+	#[inline]
+	#[must_use]
+	pub fn width(self) -> Coordinate {
+		self.width.try_into().unwrap_or_else(|_| unreachable!())
+	}
+
+	/// The height of the bounding box.
+	#[inline]
+	#[must_use]
+	pub fn height(self) -> Coordinate {
+		self.height.try_into().unwrap_or_else(|_| unreachable!())
 	}
 
 	#[must_use]
 	pub fn num_squares(self) -> usize {
 		self
-			.bits
+			.rows
 			.into_iter()
-			.map(|byte| usize::try_from(byte.count_ones()).unwrap_or_else(|_| unreachable!()))
+			.map(|row| usize::try_from(row.count_ones()).unwrap_or_else(|_| unreachable!()))
 			.sum()
 	}
 
+	/// Returns `None` if `(x, y)` is outside of the bounding box.
 	#[must_use]
 	pub fn at(self, x: Coordinate, y: Coordinate) -> Option<bool> {
-		if x < 0 || y < 0 || x >= MINO_SIZE || y >= MINO_SIZE {
+		if x < 0 || y < 0 || x >= self.width() || y >= self.height() {
 			return None;
 		}
 
-		let index = y * MINO_SIZE + x;
-		let byte = usize::try_from(index / 8).unwrap_or_else(|_| unreachable!());
-		let bit = index % 8;
-		let ret = (self.bits[byte] & (1 << bit)) > 0;
-		Some(ret)
+		let row = self.rows[usize::try_from(y).unwrap_or_else(|_| unreachable!())];
+		Some((row & (1 << x)) > 0)
+	}
+
+	/// The filled squares of row `y`, as a bitmask where bit `x` corresponds to the square at `(x, y)`.
+	#[inline]
+	#[must_use]
+	pub(crate) fn row(self, y: Coordinate) -> u16 {
+		self.rows[usize::try_from(y).unwrap_or_else(|_| unreachable!())]
 	}
 
 	/// The function receives `(x, y)` and returns whether that square in the mino is filled.
-	fn from_fn(mut is_filled: impl FnMut(Coordinate, Coordinate) -> bool) -> Self {
-		make_mino!(
-			is_filled(0, 0), is_filled(1, 0), is_filled(2, 0), is_filled(3, 0), is_filled(4, 0);
-			is_filled(0, 1), is_filled(1, 1), is_filled(2, 1), is_filled(3, 1), is_filled(4, 1);
-			is_filled(0, 2), is_filled(1, 2), is_filled(2, 2), is_filled(3, 2), is_filled(4, 2);
-			is_filled(0, 3), is_filled(1, 3), is_filled(2, 3), is_filled(3, 3), is_filled(4, 3);
-			is_filled(0, 4), is_filled(1, 4), is_filled(2, 4), is_filled(3, 4), is_filled(4, 4);
-		)
+	///
+	/// # Panics
+	///
+	/// Panics if `width` or `height` are not in `1..=MAX_MINO_SIZE`.
+	pub(crate) fn from_fn(
+		width: Coordinate,
+		height: Coordinate,
+		mut is_filled: impl FnMut(Coordinate, Coordinate) -> bool,
+	) -> Self {
+		let valid_size = 1..=MAX_MINO_SIZE;
+		assert!(
+			valid_size.contains(&width) && valid_size.contains(&height),
+			"invalid mino size"
+		);
+
+		let mut rows = [0; MAX_MINO_SIZE as usize];
+		for (y, row) in (0..height).zip(&mut rows) {
+			for x in 0..width {
+				*row |= u16::from(is_filled(x, y)) << x;
+			}
+		}
+
+		Self {
+			width: width.try_into().unwrap_or_else(|_| unreachable!()),
+			height: height.try_into().unwrap_or_else(|_| unreachable!()),
+			rows,
+		}
 	}
 
 	#[inline]
 	#[must_use]
 	pub fn flip_horizontal(self) -> Self {
-		Self::from_fn(|x, y| {
+		Self::from_fn(self.width(), self.height(), |x, y| {
 			self
-				.at((MINO_SIZE - 1) - x, y)
+				.at((self.width() - 1) - x, y)
 				.unwrap_or_else(|| unreachable!())
 		})
 	}
@@ -121,9 +334,9 @@ impl Mino {
 	#[inline]
 	#[must_use]
 	pub fn flip_vertical(self) -> Self {
-		Self::from_fn(|x, y| {
+		Self::from_fn(self.width(), self.height(), |x, y| {
 			self
-				.at(x, (MINO_SIZE - 1) - y)
+				.at(x, (self.height() - 1) - y)
 				.unwrap_or_else(|| unreachable!())
 		})
 	}
@@ -131,9 +344,9 @@ impl Mino {
 	#[inline]
 	#[must_use]
 	pub fn rotate_cw_90(self) -> Self {
-		Self::from_fn(|x, y| {
+		Self::from_fn(self.height(), self.width(), |x, y| {
 			self
-				.at(y, (MINO_SIZE - 1) - x)
+				.at(y, (self.height() - 1) - x)
 				.unwrap_or_else(|| unreachable!())
 		})
 	}
@@ -141,9 +354,9 @@ impl Mino {
 	#[inline]
 	#[must_use]
 	pub fn rotate_180(self) -> Self {
-		Self::from_fn(|x, y| {
+		Self::from_fn(self.width(), self.height(), |x, y| {
 			self
-				.at((MINO_SIZE - 1) - x, (MINO_SIZE - 1) - y)
+				.at((self.width() - 1) - x, (self.height() - 1) - y)
 				.unwrap_or_else(|| unreachable!())
 		})
 	}
@@ -151,21 +364,88 @@ impl Mino {
 	#[inline]
 	#[must_use]
 	pub fn rotate_ccw_90(self) -> Self {
-		Self::from_fn(|x, y| {
+		Self::from_fn(self.height(), self.width(), |x, y| {
+			self
+				.at((self.width() - 1) - y, x)
+				.unwrap_or_else(|| unreachable!())
+		})
+	}
+
+	/// Mirror the mino across its main diagonal, swapping the width and height of the bounding box.
+	#[inline]
+	#[must_use]
+	pub fn transpose(self) -> Self {
+		Self::from_fn(self.height(), self.width(), |x, y| {
+			self.at(y, x).unwrap_or_else(|| unreachable!())
+		})
+	}
+
+	/// Shift the shape to the top-left corner of its bounding box.
+	#[must_use]
+	pub fn normalize(self) -> Self {
+		let (min_x, min_y) = self.min_point();
+		Self::from_fn(self.width(), self.height(), |x, y| {
+			self.at(x + min_x, y + min_y).unwrap_or(false)
+		})
+	}
+
+	/// The shape within its tightest bounding box.
+	fn trim(self) -> Self {
+		let (min_x, min_y) = self.min_point();
+		let (max_x, max_y) = self.max_point();
+		Self::from_fn(max_x - min_x + 1, max_y - min_y + 1, |x, y| {
 			self
-				.at((MINO_SIZE - 1) - y, x)
+				.at(x + min_x, y + min_y)
 				.unwrap_or_else(|| unreachable!())
 		})
 	}
 
+	/// The mino in each of `Orientation::ALL`, including duplicates.
+	pub(crate) fn transforms(self) -> impl ExactSizeIterator<Item = Self> + Clone {
+		Orientation::ALL
+			.map(|orientation| orientation.apply(self))
+			.into_iter()
+	}
+
+	/// The distinct shapes that the mino can be flipped and rotated into, starting with the mino itself.
+	///
+	/// Shapes are compared regardless of where they are within the bounding box,
+	/// so there is one orientation for a symmetric shape like the plus and up to eight for an asymmetric one.
+	#[must_use]
+	pub fn orientations(self) -> Vec<Self> {
+		let mut shapes = Vec::with_capacity(8);
+		let mut ret = Vec::with_capacity(8);
+		for mino in self.transforms() {
+			let shape = mino.trim();
+			if !shapes.contains(&shape) {
+				shapes.push(shape);
+				ret.push(mino);
+			}
+		}
+		ret
+	}
+
+	/// A representative of all flips and rotations of the shape, within its tightest bounding box.
+	///
+	/// Two minos have the same canonical form if and only if one can be flipped and rotated into the other,
+	/// regardless of the size of their bounding boxes.
+	#[must_use]
+	pub fn canonical(self) -> Self {
+		self
+			.transforms()
+			.map(Self::trim)
+			.min_by_key(|mino| (mino.width, mino.height, mino.rows))
+			.unwrap_or_else(|| unreachable!())
+	}
+
 	#[inline]
 	#[must_use]
 	pub fn min_point(self) -> (Coordinate, Coordinate) {
-		let min_x = (0..MINO_SIZE)
-			.find(|&x| (0..MINO_SIZE).any(|y| self.at(x, y).unwrap_or_else(|| unreachable!())))
+		let min_x = (0..self.width())
+			.find(|&x| (0..self.height()).any(|y| self.at(x, y).unwrap_or_else(|| unreachable!())))
 			.unwrap_or_else(|| unreachable!());
-		let min_y = (0..MINO_SIZE)
-			.find(|&y| (0..MINO_SIZE).any(|x| self.at(x, y).unwrap_or_else(|| unreachable!())))
+		let min_y = (0..self.height())
+			.find(|&y| (0..self.width()).any(|x| self.at(x, y).unwrap_or_else(|| unreachable!())))
 			.unwrap_or_else(|| unreachable!());
 		(min_x, min_y)
 	}
@@ -173,20 +453,20 @@ impl Mino {
 	#[inline]
 	#[must_use]
 	pub fn max_point(self) -> (Coordinate, Coordinate) {
-		let max_x = (0..MINO_SIZE)
+		let max_x = (0..self.width())
 			.rev()
-			.find(|&x| (0..MINO_SIZE).any(|y| self.at(x, y).unwrap_or_else(|| unreachable!())))
+			.find(|&x| (0..self.height()).any(|y| self.at(x, y).unwrap_or_else(|| unreachable!())))
 			.unwrap_or_else(|| unreachable!());
-		let max_y = (0..MINO_SIZE)
+		let max_y = (0..self.height())
 			.rev()
-			.find(|&y| (0..MINO_SIZE).any(|x| self.at(x, y).unwrap_or_else(|| unreachable!())))
+			.find(|&y| (0..self.width()).any(|x| self.at(x, y).unwrap_or_else(|| unreachable!())))
 			.unwrap_or_else(|| unreachable!());
 		(max_x, max_y)
 	}
 
 	pub fn iter(self) -> impl Iterator<Item = (Coordinate, Coordinate)> + Clone {
-		(0..MINO_SIZE).flat_map(move |y| {
-			(0..MINO_SIZE)
+		(0..self.height()).flat_map(move |y| {
+			(0..self.width())
 				.map(move |x| (x, y))
 				.filter(move |&(x, y)| self.at(x, y).unwrap_or_else(|| unreachable!()))
 		})
@@ -202,13 +482,13 @@ fn test_mino_at_and_from_fn() {
 		1,1,1,1,1;
 		1,1,1,1,1;
 	);
-	for y in 0..MINO_SIZE {
-		for x in 0..MINO_SIZE {
+	for y in 0..5 {
+		for x in 0..5 {
 			let expected = true;
 			assert_eq!(t1.at(x, y).unwrap(), expected);
 		}
 	}
-	assert_eq!(Mino::from_fn(|x, y| t1.at(x, y).unwrap()), t1);
+	assert_eq!(Mino::from_fn(5, 5, |x, y| t1.at(x, y).unwrap()), t1);
 
 	let t2 = make_mino!(
 		1,1,1,1,0;
@@ -217,13 +497,13 @@ fn test_mino_at_and_from_fn() {
 		1,1,1,1,0;
 		1,1,1,1,0;
 	);
-	for y in 0..MINO_SIZE {
-		for x in 0..MINO_SIZE {
+	for y in 0..5 {
+		for x in 0..5 {
 			let expected = x != 4;
 			assert_eq!(t2.at(x, y).unwrap(), expected);
 		}
 	}
-	assert_eq!(Mino::from_fn(|x, y| t2.at(x, y).unwrap()), t2);
+	assert_eq!(Mino::from_fn(5, 5, |x, y| t2.at(x, y).unwrap()), t2);
 }
 
 #[test]
@@ -292,130 +572,211 @@ fn test_mino_transformations() {
 	);
 }
 
-const TILES: &[Mino] = &[
+#[test]
+fn test_mino_orientations() {
+	let parse = |shape: &str| shape.parse::<Mino>().unwrap();
+	let tile = |name: &str| name.parse::<MinoKind>().unwrap().mino();
+
+	assert_eq!(parse("###\n#..").transpose(), parse("##\n#.\n#."));
+	assert_eq!(parse("...\n.#.\n.##").normalize(), parse("#..\n##.\n..."));
+
+	for (name, expected) in [
+		("plus", 1),
+		("square", 1),
+		("single", 1),
+		("bar-3", 2),
+		("l-2x2", 4),
+		("l-3x3", 4),
+		("s", 4),
+		("t-3", 4),
+	] {
+		let orientations = tile(name).orientations();
+		assert_eq!(orientations.len(), expected, "{name}");
+		assert_eq!(orientations[0], tile(name));
+		assert!(orientations
+			.iter()
+			.all(|mino| mino.canonical() == tile(name).canonical()));
+	}
+	assert_eq!(parse("###\n#..").orientations().len(), 8);
+
+	assert_eq!(tile("bar-3").canonical(), parse("#\n#\n#"));
+	assert_eq!(parse("###").canonical(), parse("#\n#\n#"));
+	assert_ne!(parse("##.\n.##").canonical(), parse("###").canonical());
+}
+
+/// The built-in tiles, in the order of `MinoKind::ALL`.
+pub(crate) const TILES: &[(MinoKind, Mino)] = &[
 	// 3x3 L
-	make_mino!(
-		0,0,0,0,0;
-		0,1,0,0,0;
-		0,1,0,0,0;
-		0,1,1,1,0;
-		0,0,0,0,0;
+	(
+		MinoKind::L3x3,
+		make_mino!(
+			0,0,0,0,0;
+			0,1,0,0,0;
+			0,1,0,0,0;
+			0,1,1,1,0;
+			0,0,0,0,0;
+		),
 	),
 	// 3x2 L
-	make_mino!(
-		0,0,0,0,0;
-		0,1,0,0,0;
-		0,1,1,1,0;
-		0,0,0,0,0;
-		0,0,0,0,0;
+	(
+		MinoKind::L3x2,
+		make_mino!(
+			0,0,0,0,0;
+			0,1,0,0,0;
+			0,1,1,1,0;
+			0,0,0,0,0;
+			0,0,0,0,0;
+		),
 	),
 	// 2x2 L
-	make_mino!(
-		0,0,0,0,0;
-		0,0,1,0,0;
-		0,0,1,1,0;
-		0,0,0,0,0;
-		0,0,0,0,0;
+	(
+		MinoKind::L2x2,
+		make_mino!(
+			0,0,0,0,0;
+			0,0,1,0,0;
+			0,0,1,1,0;
+			0,0,0,0,0;
+			0,0,0,0,0;
+		),
 	),
 	// 1x1
-	make_mino!(
-		0,0,0,0,0;
-		0,0,0,0,0;
-		0,0,1,0,0;
-		0,0,0,0,0;
-		0,0,0,0,0;
+	(
+		MinoKind::Single,
+		make_mino!(
+			0,0,0,0,0;
+			0,0,0,0,0;
+			0,0,1,0,0;
+			0,0,0,0,0;
+			0,0,0,0,0;
+		),
 	),
 	// 2x2
-	make_mino!(
-		0,0,0,0,0;
-		0,0,0,0,0;
-		0,0,1,1,0;
-		0,0,1,1,0;
-		0,0,0,0,0;
+	(
+		MinoKind::Square,
+		make_mino!(
+			0,0,0,0,0;
+			0,0,0,0,0;
+			0,0,1,1,0;
+			0,0,1,1,0;
+			0,0,0,0,0;
+		),
 	),
 	// 2 diagonal
-	make_mino!(
-		0,0,0,0,0;
-		0,0,0,0,0;
-		0,0,1,0,0;
-		0,0,0,1,0;
-		0,0,0,0,0;
+	(
+		MinoKind::Diagonal2,
+		make_mino!(
+			0,0,0,0,0;
+			0,0,0,0,0;
+			0,0,1,0,0;
+			0,0,0,1,0;
+			0,0,0,0,0;
+		),
 	),
 	// 3 diagonal
-	make_mino!(
-		0,0,0,0,0;
-		0,1,0,0,0;
-		0,0,1,0,0;
-		0,0,0,1,0;
-		0,0,0,0,0;
+	(
+		MinoKind::Diagonal3,
+		make_mino!(
+			0,0,0,0,0;
+			0,1,0,0,0;
+			0,0,1,0,0;
+			0,0,0,1,0;
+			0,0,0,0,0;
+		),
 	),
 	// 2, 3, 4, 5 bar
-	make_mino!(
-		0,0,0,0,0;
-		0,0,0,0,0;
-		0,1,1,0,0;
-		0,0,0,0,0;
-		0,0,0,0,0;
+	(
+		MinoKind::Bar2,
+		make_mino!(
+			0,0,0,0,0;
+			0,0,0,0,0;
+			0,1,1,0,0;
+			0,0,0,0,0;
+			0,0,0,0,0;
+		),
 	),
-	make_mino!(
-		0,0,0,0,0;
-		0,0,0,0,0;
-		0,1,1,1,0;
-		0,0,0,0,0;
-		0,0,0,0,0;
+	(
+		MinoKind::Bar3,
+		make_mino!(
+			0,0,0,0,0;
+			0,0,0,0,0;
+			0,1,1,1,0;
+			0,0,0,0,0;
+			0,0,0,0,0;
+		),
 	),
-	make_mino!(
-		0,0,0,0,0;
-		0,0,0,0,0;
-		1,1,1,1,0;
-		0,0,0,0,0;
-		0,0,0,0,0;
+	(
+		MinoKind::Bar4,
+		make_mino!(
+			0,0,0,0,0;
+			0,0,0,0,0;
+			1,1,1,1,0;
+			0,0,0,0,0;
+			0,0,0,0,0;
+		),
 	),
-	make_mino!(
-		0,0,0,0,0;
-		0,0,0,0,0;
-		1,1,1,1,1;
-		0,0,0,0,0;
-		0,0,0,0,0;
+	(
+		MinoKind::Bar5,
+		make_mino!(
+			0,0,0,0,0;
+			0,0,0,0,0;
+			1,1,1,1,1;
+			0,0,0,0,0;
+			0,0,0,0,0;
+		),
 	),
 	// 2x3 C
-	make_mino!(
-		0,0,0,0,0;
-		0,1,1,0,0;
-		0,1,0,0,0;
-		0,1,1,0,0;
-		0,0,0,0,0;
+	(
+		MinoKind::C,
+		make_mino!(
+			0,0,0,0,0;
+			0,1,1,0,0;
+			0,1,0,0,0;
+			0,1,1,0,0;
+			0,0,0,0,0;
+		),
 	),
 	// 2x3 S and Z (¯|_)
-	make_mino!(
-		0,0,0,0,0;
-		0,1,1,0,0;
-		0,0,1,1,0;
-		0,0,0,0,0;
-		0,0,0,0,0;
+	(
+		MinoKind::S,
+		make_mino!(
+			0,0,0,0,0;
+			0,1,1,0,0;
+			0,0,1,1,0;
+			0,0,0,0,0;
+			0,0,0,0,0;
+		),
 	),
 	// 2-, 3-tall T
-	make_mino!(
-		0,0,0,0,0;
-		0,1,1,1,0;
-		0,0,1,0,0;
-		0,0,0,0,0;
-		0,0,0,0,0;
+	(
+		MinoKind::T2,
+		make_mino!(
+			0,0,0,0,0;
+			0,1,1,1,0;
+			0,0,1,0,0;
+			0,0,0,0,0;
+			0,0,0,0,0;
+		),
 	),
-	make_mino!(
-		0,0,0,0,0;
-		0,1,1,1,0;
-		0,0,1,0,0;
-		0,0,1,0,0;
-		0,0,0,0,0;
+	(
+		MinoKind::T3,
+		make_mino!(
+			0,0,0,0,0;
+			0,1,1,1,0;
+			0,0,1,0,0;
+			0,0,1,0,0;
+			0,0,0,0,0;
+		),
 	),
 	// 3x3 plus
-	make_mino!(
-		0,0,0,0,0;
-		0,0,1,0,0;
-		0,1,1,1,0;
-		0,0,1,0,0;
-		0,0,0,0,0;
+	(
+		MinoKind::Plus,
+		make_mino!(
+			0,0,0,0,0;
+			0,0,1,0,0;
+			0,1,1,1,0;
+			0,0,1,0,0;
+			0,0,0,0,0;
+		),
 	),
 ];
 
@@ -432,3 +793,71 @@ fn test_min_max_points() {
 	assert_eq!(mino.min_point(), (1, 1));
 	assert_eq!(mino.max_point(), (3, 3));
 }
+
+#[test]
+fn test_large_minos() {
+	let bar = Mino::from_fn(7, 1, |_, _| true);
+	assert_eq!(bar.num_squares(), 7);
+	assert_eq!((bar.width(), bar.height()), (7, 1));
+	let vertical = bar.rotate_cw_90();
+	assert_eq!((vertical.width(), vertical.height()), (1, 7));
+	assert_eq!(vertical.max_point(), (0, 6));
+	assert_eq!(vertical.rotate_ccw_90(), bar);
+	assert_eq!(bar.at(7, 0), None);
+
+	let hook = make_mino!(
+		1,0,0,0,0,0;
+		1,0,0,0,0,0;
+		1,1,1,1,1,1;
+	);
+	assert_eq!(
+		hook.rotate_cw_90(),
+		make_mino!(
+			1,1,1;
+			1,0,0;
+			1,0,0;
+			1,0,0;
+			1,0,0;
+			1,0,0;
+		),
+	);
+	assert_eq!(hook.rotate_180().rotate_180(), hook);
+	assert_eq!(hook.flip_horizontal().min_point(), (0, 0));
+	assert_eq!(hook.flip_horizontal().max_point(), (5, 2));
+}
+
+#[test]
+fn test_mino_text_format() {
+	let mino: Mino = "
+		.#.
+		###
+		.#.
+	"
+	.parse()
+	.unwrap();
+	assert_eq!(mino.to_string(), ".#.\n###\n.#.");
+	assert_eq!((mino.width(), mino.height()), (3, 3));
+	assert_eq!(mino.num_squares(), 5);
+	for tile in Mino::all() {
+		assert_eq!(tile.to_string().parse::<Mino>(), Ok(tile));
+	}
+
+	assert_eq!("".parse::<Mino>(), Err(MinoParseError::Empty));
+	assert_eq!("..\n..".parse::<Mino>(), Err(MinoParseError::NoSquares));
+	assert_eq!(
+		"##\n#".parse::<Mino>(),
+		Err(MinoParseError::RaggedRow { row: 2 })
+	);
+	assert_eq!(
+		"#x".parse::<Mino>(),
+		Err(MinoParseError::InvalidChar {
+			row: 1,
+			column: 2,
+			char: 'x'
+		})
+	);
+	assert_eq!(
+		"#".repeat(17).parse::<Mino>(),
+		Err(MinoParseError::TooLarge)
+	);
+}
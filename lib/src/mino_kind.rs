@@ -0,0 +1,322 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use crate::mino::{Mino, TILES};
+
+/// One of the built-in tiles, as returned by `Mino::all`.
+///
+/// Each kind has a name, like `l-3x3`, and a short code made of a letter for the shape and its number of squares, like `L5`.
+/// Both are accepted by `FromStr`; `Display` writes the name.
+///
+/// With the `serde` feature, kinds are serialized as their name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(into = "&'static str", try_from = "String")
+)]
+pub enum MinoKind {
+	/// An L with arms of length 3.
+	L3x3,
+	/// An L with a long arm of length 3 and a short arm of length 2.
+	L3x2,
+	/// An L with arms of length 2.
+	L2x2,
+	Single,
+	/// A 2x2 square.
+	Square,
+	/// Two squares touching diagonally.
+	Diagonal2,
+	/// Three squares in a diagonal line.
+	Diagonal3,
+	Bar2,
+	Bar3,
+	Bar4,
+	Bar5,
+	C,
+	S,
+	/// A T with a stem of length 1.
+	T2,
+	/// A T with a stem of length 2.
+	T3,
+	Plus,
+}
+
+impl MinoKind {
+	/// Every kind, in the order of `Mino::all`.
+	pub const ALL: [Self; 16] = [
+		Self::L3x3,
+		Self::L3x2,
+		Self::L2x2,
+		Self::Single,
+		Self::Square,
+		Self::Diagonal2,
+		Self::Diagonal3,
+		Self::Bar2,
+		Self::Bar3,
+		Self::Bar4,
+		Self::Bar5,
+		Self::C,
+		Self::S,
+		Self::T2,
+		Self::T3,
+		Self::Plus,
+	];
+
+	/// The name of the tile in the built-in `MinoSet`, such as `l-3x3` or `plus`.
+	#[must_use]
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::L3x3 => "l-3x3",
+			Self::L3x2 => "l-3x2",
+			Self::L2x2 => "l-2x2",
+			Self::Single => "single",
+			Self::Square => "square",
+			Self::Diagonal2 => "diagonal-2",
+			Self::Diagonal3 => "diagonal-3",
+			Self::Bar2 => "bar-2",
+			Self::Bar3 => "bar-3",
+			Self::Bar4 => "bar-4",
+			Self::Bar5 => "bar-5",
+			Self::C => "c",
+			Self::S => "s",
+			Self::T2 => "t-2",
+			Self::T3 => "t-3",
+			Self::Plus => "plus",
+		}
+	}
+
+	/// A two-character code: a letter for the shape followed by the number of squares, such as `L5` or `X5`.
+	#[must_use]
+	pub fn code(self) -> &'static str {
+		match self {
+			Self::L3x3 => "L5",
+			Self::L3x2 => "L4",
+			Self::L2x2 => "L3",
+			Self::Single => "O1",
+			Self::Square => "O4",
+			Self::Diagonal2 => "D2",
+			Self::Diagonal3 => "D3",
+			Self::Bar2 => "I2",
+			Self::Bar3 => "I3",
+			Self::Bar4 => "I4",
+			Self::Bar5 => "I5",
+			Self::C => "C5",
+			Self::S => "S4",
+			Self::T2 => "T4",
+			Self::T3 => "T5",
+			Self::Plus => "X5",
+		}
+	}
+
+	/// The tile in its default orientation.
+	#[inline]
+	#[must_use]
+	pub fn mino(self) -> Mino {
+		TILES[self as usize].1
+	}
+}
+
+impl Display for MinoKind {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		formatter.write_str(self.name())
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MinoKindParseError;
+
+impl Display for MinoKindParseError {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		formatter.write_str("unknown mino kind; expected the name or code of a built-in tile")
+	}
+}
+
+impl std::error::Error for MinoKindParseError {}
+
+impl FromStr for MinoKind {
+	type Err = MinoKindParseError;
+
+	/// Accepts either the name or the code of the kind.
+	fn from_str(name: &str) -> Result<Self, Self::Err> {
+		Self::ALL
+			.into_iter()
+			.find(|kind| kind.name() == name || kind.code() == name)
+			.ok_or(MinoKindParseError)
+	}
+}
+
+#[cfg(feature = "serde")]
+impl From<MinoKind> for &'static str {
+	fn from(kind: MinoKind) -> Self {
+		kind.name()
+	}
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<String> for MinoKind {
+	type Error = MinoKindParseError;
+
+	fn try_from(name: String) -> Result<Self, Self::Error> {
+		name.parse()
+	}
+}
+
+/// A combination of flips and rotations: a mirror image if `flipped`, then `quarter_turns` clockwise quarter turns.
+///
+/// With the `serde` feature, orientations are serialized as `{ "flipped": flipped, "quarter_turns": quarter_turns }`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Orientation {
+	flipped: bool,
+	#[cfg_attr(
+		feature = "serde",
+		serde(deserialize_with = "deserialize_quarter_turns")
+	)]
+	quarter_turns: u8,
+}
+
+#[cfg(feature = "serde")]
+fn deserialize_quarter_turns<'de, D: serde::Deserializer<'de>>(
+	deserializer: D,
+) -> Result<u8, D::Error> {
+	let quarter_turns = <u8 as serde::Deserialize>::deserialize(deserializer)?;
+	if quarter_turns >= 4 {
+		return Err(serde::de::Error::custom(
+			"the number of quarter turns must be less than 4",
+		));
+	}
+	Ok(quarter_turns)
+}
+
+impl Orientation {
+	/// The mino as it is.
+	pub const IDENTITY: Self = Self {
+		flipped: false,
+		quarter_turns: 0,
+	};
+
+	/// All eight orientations, unflipped ones first.
+	pub const ALL: [Self; 8] = {
+		let mut ret = [Self::IDENTITY; 8];
+		let mut index = 0;
+		while index < 8 {
+			ret[index] = Self {
+				flipped: index >= 4,
+				#[allow(clippy::cast_possible_truncation /* less than 4 */)]
+				quarter_turns: (index % 4) as u8,
+			};
+			index += 1;
+		}
+		ret
+	};
+
+	/// `quarter_turns` is taken modulo 4.
+	#[inline]
+	#[must_use]
+	pub fn new(flipped: bool, quarter_turns: u8) -> Self {
+		Self {
+			flipped,
+			quarter_turns: quarter_turns % 4,
+		}
+	}
+
+	/// Whether the mino is mirrored horizontally before being rotated.
+	#[inline]
+	#[must_use]
+	pub fn flipped(self) -> bool {
+		self.flipped
+	}
+
+	/// The number of clockwise quarter turns, from 0 to 3.
+	#[inline]
+	#[must_use]
+	pub fn quarter_turns(self) -> u8 {
+		self.quarter_turns
+	}
+
+	#[must_use]
+	pub fn apply(self, mino: Mino) -> Mino {
+		let mino = if self.flipped {
+			mino.flip_horizontal()
+		} else {
+			mino
+		};
+		match self.quarter_turns {
+			0 => mino,
+			1 => mino.rotate_cw_90(),
+			2 => mino.rotate_180(),
+			3 => mino.rotate_ccw_90(),
+			_ => unreachable!(),
+		}
+	}
+}
+
+/// For example, `upright`, `rotated 90°` or `flipped and rotated 180°`.
+impl Display for Orientation {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		let degrees = u16::from(self.quarter_turns) * 90;
+		match (self.flipped, degrees) {
+			(false, 0) => formatter.write_str("upright"),
+			(true, 0) => formatter.write_str("flipped"),
+			(false, degrees) => write!(formatter, "rotated {degrees}°"),
+			(true, degrees) => write!(formatter, "flipped and rotated {degrees}°"),
+		}
+	}
+}
+
+#[test]
+fn test_mino_kinds() {
+	assert!(TILES.iter().map(|&(kind, _)| kind).eq(MinoKind::ALL));
+	for kind in MinoKind::ALL {
+		assert_eq!(kind.name().parse(), Ok(kind));
+		assert_eq!(kind.code().parse(), Ok(kind));
+		assert_eq!(
+			kind.code()[1..].parse::<usize>().unwrap(),
+			kind.mino().num_squares(),
+			"{kind}"
+		);
+		for orientation in Orientation::ALL {
+			let (identified, identified_orientation) = orientation.apply(kind.mino()).identify().unwrap();
+			assert_eq!(identified, kind);
+			assert_eq!(
+				identified_orientation.apply(kind.mino()),
+				orientation.apply(kind.mino())
+			);
+		}
+	}
+	assert_eq!("X".parse::<MinoKind>(), Err(MinoKindParseError));
+
+	let t = MinoKind::T2.mino();
+	assert_eq!(
+		t.rotate_cw_90().identify(),
+		Some((MinoKind::T2, Orientation::new(false, 1)))
+	);
+	// Identification ignores the bounding box.
+	assert_eq!(
+		".#.\n###".parse::<Mino>().unwrap().identify(),
+		Some((MinoKind::T2, Orientation::new(false, 2)))
+	);
+	assert_eq!("##\n##\n#.".parse::<Mino>().unwrap().identify(), None);
+
+	assert_eq!(Orientation::new(false, 1).to_string(), "rotated 90°");
+	assert_eq!(
+		Orientation::new(true, 6).to_string(),
+		"flipped and rotated 180°"
+	);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_mino_kind_serde() {
+	assert_eq!(
+		serde_json::to_string(&MinoKind::L3x2).unwrap(),
+		r#""l-3x2""#
+	);
+	assert_eq!(
+		serde_json::from_str::<MinoKind>(r#""plus""#).unwrap(),
+		MinoKind::Plus
+	);
+	assert!(serde_json::from_str::<MinoKind>(r#""hexomino""#).is_err());
+	assert!(serde_json::from_str::<Orientation>(r#"{"flipped":true,"quarter_turns":4}"#).is_err());
+}
@@ -0,0 +1,295 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use crate::mino::{Mino, MinoParseError};
+
+/// A non-empty set of named tiles for generators to pick from.
+///
+/// Mino set files consist of pieces, each of which is a `[name]` header followed by its shape in the format of `Mino`'s `FromStr` impl.
+/// Blank lines and lines starting with `;` are ignored:
+///
+/// ```text
+/// ; A T and a long bar.
+/// [t]
+/// ###
+/// .#.
+///
+/// [bar-6]
+/// ######
+/// ```
+///
+/// With the `serde` feature, mino sets are serialized as an array of `{ "name": name, "mino": mino }` objects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(into = "Vec<PieceRepr>", try_from = "Vec<PieceRepr>")
+)]
+pub struct MinoSet {
+	names: Vec<String>,
+	minos: Vec<Mino>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct PieceRepr {
+	name: String,
+	mino: Mino,
+}
+
+#[cfg(feature = "serde")]
+impl From<MinoSet> for Vec<PieceRepr> {
+	fn from(set: MinoSet) -> Self {
+		set
+			.names
+			.into_iter()
+			.zip(set.minos)
+			.map(|(name, mino)| PieceRepr { name, mino })
+			.collect()
+	}
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<Vec<PieceRepr>> for MinoSet {
+	type Error = String;
+
+	fn try_from(pieces: Vec<PieceRepr>) -> Result<Self, Self::Error> {
+		if pieces.is_empty() {
+			return Err(MinoSetParseError::Empty.to_string());
+		}
+		let mut ret = Self {
+			names: Vec::with_capacity(pieces.len()),
+			minos: Vec::with_capacity(pieces.len()),
+		};
+		for PieceRepr { name, mino } in pieces {
+			if ret.names.contains(&name) {
+				return Err(format!("duplicate piece name {name:?}"));
+			}
+			ret.names.push(name);
+			ret.minos.push(mino);
+		}
+		Ok(ret)
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MinoSetParseError {
+	/// There are no pieces.
+	Empty,
+	/// A shape was found before the first `[name]` header.
+	MissingName { line: usize },
+	/// A `[` header is not closed or has an empty name.
+	InvalidHeader { line: usize },
+	/// Two pieces have the same name.
+	DuplicateName { line: usize, name: String },
+	/// The shape of a piece is invalid. The line is that of the piece's header.
+	InvalidMino {
+		line: usize,
+		name: String,
+		error: MinoParseError,
+	},
+}
+
+impl Display for MinoSetParseError {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Empty => formatter.write_str("the mino set has no pieces"),
+			Self::MissingName { line } => write!(
+				formatter,
+				"line {line}: shapes must follow a `[name]` header"
+			),
+			Self::InvalidHeader { line } => write!(
+				formatter,
+				"line {line}: headers must be of the form `[name]`"
+			),
+			Self::DuplicateName { line, name } => {
+				write!(formatter, "line {line}: duplicate piece name {name:?}")
+			}
+			Self::InvalidMino { line, name, error } => {
+				write!(
+					formatter,
+					"line {line}: invalid shape for {name:?}: {error}"
+				)
+			}
+		}
+	}
+}
+
+impl std::error::Error for MinoSetParseError {}
+
+impl Default for MinoSet {
+	fn default() -> Self {
+		Self::builtin()
+	}
+}
+
+impl MinoSet {
+	/// The built-in tiles, as returned by `Mino::all`.
+	#[must_use]
+	pub fn builtin() -> Self {
+		let (names, minos) = Mino::named()
+			.map(|(name, mino)| (name.to_owned(), mino))
+			.unzip();
+		Self { names, minos }
+	}
+
+	/// The number of pieces, which is never zero.
+	#[inline]
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.minos.len()
+	}
+
+	/// Always `false`; provided for consistency with `len`.
+	#[inline]
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.minos.is_empty()
+	}
+
+	#[inline]
+	#[must_use]
+	pub fn minos(&self) -> &[Mino] {
+		&self.minos
+	}
+
+	/// All pieces, with their names, in the order they were defined.
+	pub fn iter(&self) -> impl ExactSizeIterator<Item = (&str, Mino)> + Clone + '_ {
+		self
+			.names
+			.iter()
+			.map(String::as_str)
+			.zip(self.minos.iter().copied())
+	}
+
+	/// Look up a piece by name.
+	#[must_use]
+	pub fn get(&self, name: &str) -> Option<Mino> {
+		self
+			.iter()
+			.find(|&(piece, _)| piece == name)
+			.map(|(_, mino)| mino)
+	}
+}
+
+/// The format accepted by `FromStr`.
+impl Display for MinoSet {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		for (index, (name, mino)) in self.iter().enumerate() {
+			if index > 0 {
+				formatter.write_str("\n\n")?;
+			}
+			write!(formatter, "[{name}]\n{mino}")?;
+		}
+		Ok(())
+	}
+}
+
+impl FromStr for MinoSet {
+	type Err = MinoSetParseError;
+
+	fn from_str(file: &str) -> Result<Self, Self::Err> {
+		// `(header line, name, shape)`
+		let mut pieces: Vec<(usize, &str, String)> = Vec::new();
+
+		for (index, line) in file.lines().enumerate() {
+			let line_number = index + 1;
+			let line = line.trim();
+			if line.is_empty() || line.starts_with(';') {
+				continue;
+			}
+
+			if let Some(header) = line.strip_prefix('[') {
+				let name = header
+					.strip_suffix(']')
+					.map(str::trim)
+					.filter(|name| !name.is_empty())
+					.ok_or(MinoSetParseError::InvalidHeader { line: line_number })?;
+				if pieces.iter().any(|&(_, existing, _)| existing == name) {
+					return Err(MinoSetParseError::DuplicateName {
+						line: line_number,
+						name: name.to_owned(),
+					});
+				}
+				pieces.push((line_number, name, String::new()));
+			} else {
+				let (_, _, shape) = pieces
+					.last_mut()
+					.ok_or(MinoSetParseError::MissingName { line: line_number })?;
+				shape.push_str(line);
+				shape.push('\n');
+			}
+		}
+
+		if pieces.is_empty() {
+			return Err(MinoSetParseError::Empty);
+		}
+
+		let mut ret = Self {
+			names: Vec::with_capacity(pieces.len()),
+			minos: Vec::with_capacity(pieces.len()),
+		};
+		for (line, name, shape) in pieces {
+			let mino = shape
+				.parse()
+				.map_err(|error| MinoSetParseError::InvalidMino {
+					line,
+					name: name.to_owned(),
+					error,
+				})?;
+			ret.names.push(name.to_owned());
+			ret.minos.push(mino);
+		}
+		Ok(ret)
+	}
+}
+
+#[test]
+fn test_mino_set_format() {
+	let set: MinoSet = "
+		; A comment.
+		[t]
+		###
+		.#.
+
+		[bar-6]
+		######
+	"
+	.parse()
+	.unwrap();
+	assert_eq!(set.len(), 2);
+	assert_eq!(set.get("bar-6").unwrap().num_squares(), 6);
+	assert_eq!(set.to_string().parse::<MinoSet>(), Ok(set));
+
+	let builtin = MinoSet::builtin();
+	assert_eq!(builtin.minos(), Mino::all().collect::<Vec<_>>());
+	assert_eq!(builtin.to_string().parse::<MinoSet>(), Ok(builtin));
+
+	assert_eq!(
+		"; nothing".parse::<MinoSet>(),
+		Err(MinoSetParseError::Empty)
+	);
+	assert_eq!(
+		"#\n[a]\n#".parse::<MinoSet>(),
+		Err(MinoSetParseError::MissingName { line: 1 })
+	);
+	assert_eq!(
+		"[a]\n#\n[a]\n#".parse::<MinoSet>(),
+		Err(MinoSetParseError::DuplicateName {
+			line: 3,
+			name: "a".to_owned()
+		})
+	);
+	assert_eq!(
+		"[a]\n#\n[b]".parse::<MinoSet>(),
+		Err(MinoSetParseError::InvalidMino {
+			line: 3,
+			name: "b".to_owned(),
+			error: MinoParseError::Empty,
+		})
+	);
+	assert_eq!(
+		"[a\n#".parse::<MinoSet>(),
+		Err(MinoSetParseError::InvalidHeader { line: 1 })
+	);
+}
@@ -0,0 +1,937 @@
+use std::fmt::{self, Display, Formatter};
+use std::iter::Peekable;
+use std::str::FromStr;
+
+#[cfg(test)]
+use crate::board::{Board, PlaceError};
+use crate::game::{Game, PlayError, BATCH_SIZE};
+use crate::generator::{AnyGenerator, GeneratorKind, HardGenerator, MinoGenerator};
+use crate::layout::{Layout, LayoutError};
+use crate::mino::Mino;
+use crate::mino_set::MinoSetParseError;
+use crate::rules::Rules;
+use crate::scoring::{ScoringKind, ScoringRule};
+use crate::util::Coordinate;
+
+/// The version of the replay format written by `Replay`'s `Display` impl.
+pub const REPLAY_VERSION: u32 = 1;
+
+/// One step of a recorded game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayMove {
+	/// `Game::play` with `index` and `position`.
+	/// `mino` is the mino that was at `index` in the batch, which lets a replay detect that it deals different minos than the recorded game.
+	Place {
+		index: usize,
+		mino: Mino,
+		position: (Coordinate, Coordinate),
+	},
+	/// `Game::undo`. Undos are kept in the replay so that it shows every move the player made.
+	Undo,
+	/// `Game::redo`.
+	Redo,
+	/// A new batch was dealt. This follows the start of the game and every placement that exhausts the batch,
+	/// so that a replay can't deal different minos than the recorded game, even ones that are never placed.
+	Deal { minos: [Mino; BATCH_SIZE] },
+	/// The player asked the bot for a hint, or to make the placement that follows. It doesn't change the game,
+	/// but is recorded so that verifying a replay reveals how much of it the bot played.
+	Hint,
+}
+
+/// A line of the `moves` block: `place <index> <x> <y> <mino>`, with the rows of the mino separated by `/`,
+/// `undo`, `redo`, `deal` followed by the minos of the batch, or `hint`.
+impl Display for ReplayMove {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Place {
+				index,
+				mino,
+				position: (x, y),
+			} => write!(
+				formatter,
+				"place {index} {x} {y} {}",
+				mino.to_string().replace('\n', "/")
+			),
+			Self::Undo => formatter.write_str("undo"),
+			Self::Redo => formatter.write_str("redo"),
+			Self::Hint => formatter.write_str("hint"),
+			Self::Deal { minos } => {
+				formatter.write_str("deal")?;
+				for mino in minos {
+					write!(formatter, " {}", mino.to_string().replace('\n', "/"))?;
+				}
+				Ok(())
+			}
+		}
+	}
+}
+
+impl ReplayMove {
+	/// The `Deal` to record for `game`'s batch if it was just dealt, which is the case when none of it has been placed.
+	///
+	/// Recorders call this at the start of the game and after each placement.
+	#[must_use]
+	pub fn deal<G: MinoGenerator + Clone, S: ScoringRule>(game: &Game<G, S>) -> Option<Self> {
+		let minos = game.minos();
+		minos.iter().all(Option::is_some).then(|| Self::Deal {
+			minos: minos.map(|mino| mino.unwrap_or_else(|| unreachable!())),
+		})
+	}
+
+	/// Make this move, which is move `number` of a replay, in `game`.
+	fn apply<G: MinoGenerator + Clone, S: ScoringRule>(
+		self,
+		game: &mut Game<G, S>,
+		number: usize,
+	) -> Result<(), ReplayError> {
+		match self {
+			Self::Place {
+				index,
+				mino,
+				position,
+			} => {
+				if let Some(dealt) = game.mino(index).filter(|&dealt| dealt != mino) {
+					return Err(ReplayError::WrongMino {
+						number,
+						recorded: mino,
+						dealt,
+					});
+				}
+				game
+					.play(index, position)
+					.map_err(|error| ReplayError::Play { number, error })?;
+			}
+			Self::Undo => {
+				if !game.undo() {
+					return Err(ReplayError::NothingToUndo { number });
+				}
+			}
+			Self::Redo => {
+				if !game.redo() {
+					return Err(ReplayError::NothingToRedo { number });
+				}
+			}
+			Self::Deal { minos } => {
+				if game.minos() != minos.map(Some) {
+					return Err(ReplayError::WrongBatch {
+						number,
+						dealt: game.minos(),
+					});
+				}
+			}
+			Self::Hint => {}
+		}
+		Ok(())
+	}
+
+	fn parse(line: &str) -> Option<Self> {
+		let mut words = line.split_whitespace();
+		let ret = match words.next()? {
+			"place" => Self::Place {
+				index: words.next()?.parse().ok()?,
+				position: (words.next()?.parse().ok()?, words.next()?.parse().ok()?),
+				mino: words.next()?.replace('/', "\n").parse().ok()?,
+			},
+			"undo" => Self::Undo,
+			"redo" => Self::Redo,
+			"hint" => Self::Hint,
+			"deal" => {
+				let minos: Vec<Mino> = words
+					.by_ref()
+					.take(BATCH_SIZE)
+					.map(|mino| mino.replace('/', "\n").parse().ok())
+					.collect::<Option<_>>()?;
+				Self::Deal {
+					minos: minos.try_into().ok()?,
+				}
+			}
+			_ => return None,
+		};
+		words.next().is_none().then_some(ret)
+	}
+}
+
+/// A recorded game: its rules, the seed of its generator, and its moves.
+///
+/// Replays are text files. `Display` writes them and `FromStr` reads them.
+/// The first line is `tetroku-replay` and the version of the format, followed by one field per line.
+/// The `layout`, `minos` and `moves` fields are followed by blocks of indented lines: a region map, a mino set and one move per line.
+/// Blank lines and lines starting with `;` are ignored:
+///
+/// ```text
+/// tetroku-replay 1
+/// seed 42
+/// ; `uniform`, `bag <copies>`, `bag <copies> orientations` or `hard <hardness>`.
+/// generator bag 2 orientations
+/// ; Optional.
+/// survivable
+/// scoring classic
+/// ; Optional; undo is unlimited if omitted.
+/// undo-limit 5
+/// ; Optional; the final score claimed by the recording.
+/// score 14
+/// layout
+///     111222333
+///     ...
+/// minos
+///     [l-2x2]
+///     #.
+///     ##
+///     ...
+/// moves
+///     deal #./## ### #
+///     place 0 3 4 #./##
+///     undo
+///     hint
+///     place 2 0 8 ###
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replay {
+	pub rules: Rules,
+	pub seed: u64,
+	pub moves: Vec<ReplayMove>,
+	/// The final score claimed by the recording, if any, which `Replay::verify` checks.
+	pub score: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ReplayError {
+	/// The placement couldn't be played. Moves are numbered from 1.
+	Play { number: usize, error: PlayError },
+	/// The batch held a different mino at the placement's index than the recorded one,
+	/// so the replay was recorded with a different seed or different rules.
+	WrongMino {
+		number: usize,
+		recorded: Mino,
+		dealt: Mino,
+	},
+	/// There was nothing to undo.
+	NothingToUndo { number: usize },
+	/// There was nothing to redo.
+	NothingToRedo { number: usize },
+	/// A `Deal` recorded a different batch than `dealt`.
+	WrongBatch {
+		number: usize,
+		dealt: [Option<Mino>; BATCH_SIZE],
+	},
+	/// A batch was dealt but the move doesn't record it with a `Deal`.
+	/// This is one past the last move if the replay ends right after a batch was dealt.
+	MissingDeal { number: usize },
+	/// A `Deal` doesn't follow the start of the game or a placement that exhausted the batch.
+	UnexpectedDeal { number: usize },
+	/// The score after the last move differs from the claimed one.
+	WrongScore { claimed: u32, actual: u32 },
+}
+
+/// `minos` with the rows of each mino separated by `/`, and `-` for the minos that have been placed.
+fn batch_text(minos: [Option<Mino>; BATCH_SIZE]) -> String {
+	minos
+		.map(|mino| {
+			mino.map_or_else(
+				|| "-".to_owned(),
+				|mino| mino.to_string().replace('\n', "/"),
+			)
+		})
+		.join(" ")
+}
+
+impl Display for ReplayError {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Play { number, error } => write!(formatter, "move {number}: {error}"),
+			Self::WrongMino {
+				number,
+				recorded,
+				dealt,
+			} => write!(
+				formatter,
+				"move {number}: the recorded mino {} doesn't match the dealt mino {}",
+				recorded.to_string().replace('\n', "/"),
+				dealt.to_string().replace('\n', "/"),
+			),
+			Self::NothingToUndo { number } => write!(formatter, "move {number}: nothing to undo"),
+			Self::NothingToRedo { number } => write!(formatter, "move {number}: nothing to redo"),
+			Self::WrongBatch { number, dealt } => write!(
+				formatter,
+				"move {number}: the recorded batch doesn't match the dealt batch {}",
+				batch_text(*dealt),
+			),
+			Self::MissingDeal { number } => write!(
+				formatter,
+				"move {number}: a batch was dealt, so this move must be `deal`"
+			),
+			Self::UnexpectedDeal { number } => write!(
+				formatter,
+				"move {number}: `deal` must follow the start of the game or a placement that exhausted the batch"
+			),
+			Self::WrongScore { claimed, actual } => write!(
+				formatter,
+				"the claimed score is {claimed}, but the moves score {actual}"
+			),
+		}
+	}
+}
+
+impl std::error::Error for ReplayError {}
+
+impl Replay {
+	/// A replay of a game with `rules` and `seed` that has no moves yet.
+	#[must_use]
+	pub fn new(rules: Rules, seed: u64) -> Self {
+		Self {
+			rules,
+			seed,
+			moves: Vec::new(),
+			score: None,
+		}
+	}
+
+	/// The game before the first move.
+	#[must_use]
+	pub fn new_game(&self) -> Game<AnyGenerator, ScoringKind> {
+		self.rules.new_game(self.seed)
+	}
+
+	/// Play the moves from the start, calling `visit` with the game before the first move and after each move,
+	/// and return the final game.
+	fn play_moves(
+		&self,
+		mut visit: impl FnMut(&Game<AnyGenerator, ScoringKind>),
+	) -> Result<Game<AnyGenerator, ScoringKind>, ReplayError> {
+		let mut game = self.new_game();
+		visit(&game);
+		// Whether a batch was just dealt, which the next move must record.
+		let mut dealt = true;
+		for (number, &game_move) in (1..).zip(&self.moves) {
+			match (dealt, game_move) {
+				(true, ReplayMove::Deal { .. }) => dealt = false,
+				(true, _) => return Err(ReplayError::MissingDeal { number }),
+				(false, ReplayMove::Deal { .. }) => return Err(ReplayError::UnexpectedDeal { number }),
+				(false, ReplayMove::Place { .. }) => {
+					dealt = game.minos().iter().flatten().count() == 1;
+				}
+				(false, ReplayMove::Undo | ReplayMove::Redo | ReplayMove::Hint) => {}
+			}
+			game_move.apply(&mut game, number)?;
+			visit(&game);
+		}
+		if dealt {
+			return Err(ReplayError::MissingDeal {
+				number: self.moves.len() + 1,
+			});
+		}
+		Ok(game)
+	}
+
+	/// Play the moves from the start, returning the game before the first move followed by the game after each move.
+	///
+	/// The returned games have no undo or redo history, so that they take constant space each.
+	///
+	/// # Errors
+	///
+	/// Fails at the first move that can't be played, that places a different mino than the one dealt,
+	/// or that doesn't record the batch that was dealt.
+	pub fn play_back(&self) -> Result<Vec<Game<AnyGenerator, ScoringKind>>, ReplayError> {
+		let mut ret = Vec::with_capacity(self.moves.len() + 1);
+		self.play_moves(|game| ret.push(game.clone_without_history()))?;
+		Ok(ret)
+	}
+
+	/// Play the moves from the start with the seeded generator and check the claimed score, if any.
+	///
+	/// Every placement must be legal and place the mino that was dealt at its index in the batch,
+	/// and every batch that was dealt must be recorded.
+	/// The rules are taken from the replay, so check them too before trusting the score; see `Rules::is_standard`.
+	///
+	/// # Errors
+	///
+	/// Fails like `play_back`, or if the final score differs from the claimed one.
+	pub fn verify(&self) -> Result<Verification, ReplayError> {
+		let game = self.play_moves(|_| {})?;
+		if let Some(claimed) = self.score.filter(|&claimed| claimed != game.score()) {
+			return Err(ReplayError::WrongScore {
+				claimed,
+				actual: game.score(),
+			});
+		}
+		let count = |kind: fn(&ReplayMove) -> bool| {
+			self
+				.moves
+				.iter()
+				.filter(|&game_move| kind(game_move))
+				.count()
+		};
+		Ok(Verification {
+			score: game.score(),
+			lost: game.lost(),
+			placements: count(|game_move| matches!(game_move, ReplayMove::Place { .. })),
+			undos: count(|game_move| matches!(game_move, ReplayMove::Undo)),
+			hints: count(|game_move| matches!(game_move, ReplayMove::Hint)),
+		})
+	}
+}
+
+/// What `Replay::verify` found out about a replay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Verification {
+	pub score: u32,
+	/// Whether the game ended, rather than being stopped while minos could still be placed.
+	pub lost: bool,
+	/// The number of placements, including ones that were undone.
+	pub placements: usize,
+	pub undos: usize,
+	/// The number of hints and placements made by the bot.
+	pub hints: usize,
+}
+
+/// Write `contents` as an indented block.
+fn write_block(formatter: &mut Formatter<'_>, contents: &dyn Display) -> fmt::Result {
+	for line in contents.to_string().lines() {
+		if line.is_empty() {
+			writeln!(formatter)?;
+		} else {
+			writeln!(formatter, "\t{line}")?;
+		}
+	}
+	Ok(())
+}
+
+/// The format accepted by `FromStr`, with the current `REPLAY_VERSION`.
+impl Display for Replay {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		let Rules {
+			generator,
+			survivable,
+			minos,
+			layout,
+			scoring,
+			undo_limit,
+		} = &self.rules;
+
+		writeln!(formatter, "tetroku-replay {REPLAY_VERSION}")?;
+		writeln!(formatter, "seed {}", self.seed)?;
+		match *generator {
+			GeneratorKind::Uniform => writeln!(formatter, "generator uniform")?,
+			GeneratorKind::Bag {
+				copies,
+				orientations,
+			} => writeln!(
+				formatter,
+				"generator bag {copies}{}",
+				if orientations { " orientations" } else { "" }
+			)?,
+			GeneratorKind::Hard { hardness } => writeln!(formatter, "generator hard {hardness}")?,
+		}
+		if *survivable {
+			writeln!(formatter, "survivable")?;
+		}
+		writeln!(formatter, "scoring {scoring}")?;
+		if let Some(limit) = undo_limit {
+			writeln!(formatter, "undo-limit {limit}")?;
+		}
+		if let Some(score) = self.score {
+			writeln!(formatter, "score {score}")?;
+		}
+		writeln!(formatter, "layout")?;
+		write_block(formatter, layout)?;
+		writeln!(formatter, "minos")?;
+		write_block(formatter, minos)?;
+		writeln!(formatter, "moves")?;
+		for game_move in &self.moves {
+			writeln!(formatter, "\t{game_move}")?;
+		}
+		Ok(())
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayParseError {
+	/// The first line isn't `tetroku-replay <version>`.
+	MissingHeader,
+	/// The replay was written with a different version of the format.
+	UnsupportedVersion {
+		version: u32,
+	},
+	/// A required field is missing.
+	MissingField {
+		field: &'static str,
+	},
+	/// A field is given more than once.
+	DuplicateField {
+		line: usize,
+		field: String,
+	},
+	UnknownField {
+		line: usize,
+		field: String,
+	},
+	/// An indented line doesn't belong to a block field.
+	UnexpectedIndent {
+		line: usize,
+	},
+	/// The value of a field is invalid.
+	InvalidValue {
+		line: usize,
+		field: String,
+	},
+	/// The region map of the `layout` block is invalid. The line is that of the `layout` field.
+	InvalidLayout {
+		line: usize,
+		error: LayoutError,
+	},
+	/// The mino set of the `minos` block is invalid. The line is that of the `minos` field,
+	/// and lines within `error` are numbered from the start of the block, not counting blank lines and comments.
+	InvalidMinos {
+		line: usize,
+		error: MinoSetParseError,
+	},
+	InvalidMove {
+		line: usize,
+	},
+}
+
+impl Display for ReplayParseError {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::MissingHeader => {
+				formatter.write_str("replays must start with `tetroku-replay <version>`")
+			}
+			Self::UnsupportedVersion { version } => write!(
+				formatter,
+				"unsupported replay version {version}; expected {REPLAY_VERSION}"
+			),
+			Self::MissingField { field } => write!(formatter, "missing field `{field}`"),
+			Self::DuplicateField { line, field } => {
+				write!(formatter, "line {line}: duplicate field `{field}`")
+			}
+			Self::UnknownField { line, field } => {
+				write!(formatter, "line {line}: unknown field `{field}`")
+			}
+			Self::UnexpectedIndent { line } => write!(
+				formatter,
+				"line {line}: only the lines of a block may be indented"
+			),
+			Self::InvalidValue { line, field } => {
+				write!(formatter, "line {line}: invalid value for `{field}`")
+			}
+			Self::InvalidLayout { line, error } => {
+				write!(formatter, "line {line}: invalid layout: {error}")
+			}
+			Self::InvalidMinos { line, error } => {
+				write!(formatter, "line {line}: invalid minos: {error}")
+			}
+			Self::InvalidMove { line } => write!(
+				formatter,
+				"line {line}: moves must be `place <index> <x> <y> <mino>`, `undo`, `redo`, `deal <minos>` or `hint`"
+			),
+		}
+	}
+}
+
+impl std::error::Error for ReplayParseError {}
+
+fn parse_generator(value: &str) -> Option<GeneratorKind> {
+	let mut words = value.split_whitespace();
+	let ret = match words.next()? {
+		"uniform" => GeneratorKind::Uniform,
+		"bag" => {
+			let copies = words.next()?.parse().ok().filter(|&copies| copies > 0)?;
+			let orientations = words.clone().next() == Some("orientations");
+			if orientations {
+				words.next();
+			}
+			GeneratorKind::Bag {
+				copies,
+				orientations,
+			}
+		}
+		"hard" => GeneratorKind::Hard {
+			hardness: words
+				.next()?
+				.parse()
+				.ok()
+				.filter(|&hardness| hardness <= HardGenerator::MAX_HARDNESS)?,
+		},
+		_ => return None,
+	};
+	words.next().is_none().then_some(ret)
+}
+
+/// The indented lines following a block field, with their line numbers.
+fn block<'a>(
+	lines: &mut Peekable<impl Iterator<Item = (usize, &'a str)>>,
+) -> Vec<(usize, &'a str)> {
+	let mut ret = Vec::new();
+	while let Some((line, text)) = lines.next_if(|(_, text)| text.starts_with(char::is_whitespace)) {
+		ret.push((line, text.trim()));
+	}
+	ret
+}
+
+fn parse_moves(block: &[(usize, &str)]) -> Result<Vec<ReplayMove>, ReplayParseError> {
+	block
+		.iter()
+		.map(|&(line, text)| ReplayMove::parse(text).ok_or(ReplayParseError::InvalidMove { line }))
+		.collect()
+}
+
+fn join_block(block: &[(usize, &str)]) -> String {
+	block
+		.iter()
+		.map(|&(_, text)| text)
+		.collect::<Vec<_>>()
+		.join("\n")
+}
+
+/// Check the `tetroku-replay <version>` header. There is only one version so far.
+fn parse_version(header: &str) -> Result<u32, ReplayParseError> {
+	let version = header
+		.strip_prefix("tetroku-replay ")
+		.and_then(|version| version.trim().parse().ok())
+		.ok_or(ReplayParseError::MissingHeader)?;
+	if !(1..=REPLAY_VERSION).contains(&version) {
+		return Err(ReplayParseError::UnsupportedVersion { version });
+	}
+	Ok(version)
+}
+
+impl FromStr for Replay {
+	type Err = ReplayParseError;
+
+	fn from_str(file: &str) -> Result<Self, Self::Err> {
+		let mut lines = file
+			.lines()
+			.enumerate()
+			.map(|(index, text)| (index + 1, text.trim_end()))
+			.filter(|(_, text)| {
+				let trimmed = text.trim_start();
+				!trimmed.is_empty() && !trimmed.starts_with(';')
+			})
+			.peekable();
+
+		let (_, header) = lines.next().ok_or(ReplayParseError::MissingHeader)?;
+		parse_version(header)?;
+
+		let mut seed = None;
+		let mut generator = None;
+		let mut survivable = false;
+		let mut scoring = None;
+		let mut undo_limit = None;
+		let mut score = None;
+		let mut layout = None;
+		let mut minos = None;
+		let mut moves = None;
+
+		let mut given: Vec<&str> = Vec::new();
+		while let Some((line, text)) = lines.next() {
+			if text.starts_with(char::is_whitespace) {
+				return Err(ReplayParseError::UnexpectedIndent { line });
+			}
+			let (field, value) = text
+				.split_once(char::is_whitespace)
+				.map_or((text, ""), |(field, value)| (field, value.trim()));
+			if given.contains(&field) {
+				return Err(ReplayParseError::DuplicateField {
+					line,
+					field: field.to_owned(),
+				});
+			}
+			given.push(field);
+
+			let invalid = || ReplayParseError::InvalidValue {
+				line,
+				field: field.to_owned(),
+			};
+			match field {
+				"survivable" | "layout" | "minos" | "moves" if !value.is_empty() => return Err(invalid()),
+				"seed" => seed = Some(value.parse().map_err(|_| invalid())?),
+				"generator" => generator = Some(parse_generator(value).ok_or_else(invalid)?),
+				"survivable" => survivable = true,
+				"scoring" => scoring = Some(value.parse().map_err(|_| invalid())?),
+				"undo-limit" => undo_limit = Some(value.parse().map_err(|_| invalid())?),
+				"score" => score = Some(value.parse().map_err(|_| invalid())?),
+				"layout" => {
+					let map = join_block(&block(&mut lines));
+					layout = Some(
+						map
+							.parse::<Layout>()
+							.map_err(|error| ReplayParseError::InvalidLayout { line, error })?
+							.intern(),
+					);
+				}
+				"minos" => {
+					let set = join_block(&block(&mut lines));
+					minos = Some(
+						set
+							.parse()
+							.map_err(|error| ReplayParseError::InvalidMinos { line, error })?,
+					);
+				}
+				"moves" => {
+					moves = Some(parse_moves(&block(&mut lines))?);
+				}
+				_ => {
+					return Err(ReplayParseError::UnknownField {
+						line,
+						field: field.to_owned(),
+					})
+				}
+			}
+		}
+
+		let missing = |field| ReplayParseError::MissingField { field };
+		Ok(Self {
+			rules: Rules {
+				generator: generator.ok_or(missing("generator"))?,
+				survivable,
+				minos: minos.ok_or(missing("minos"))?,
+				layout: layout.ok_or(missing("layout"))?,
+				scoring: scoring.ok_or(missing("scoring"))?,
+				undo_limit,
+			},
+			seed: seed.ok_or(missing("seed"))?,
+			moves: moves.ok_or(missing("moves"))?,
+			score,
+		})
+	}
+}
+
+/// Record a game with unusual rules and some undos and redos,
+/// returning the replay, the final game, and the board and score after each move.
+#[cfg(test)]
+fn record_test_game() -> (
+	Replay,
+	Game<AnyGenerator, ScoringKind>,
+	Vec<Board>,
+	Vec<u32>,
+) {
+	let rules = Rules {
+		generator: GeneratorKind::Bag {
+			copies: 2,
+			orientations: true,
+		},
+		survivable: true,
+		minos: "; Comments are dropped.\n[t]\n###\n.#.\n\n[bar-2]\n##"
+			.parse()
+			.unwrap(),
+		layout: Layout::builtin("jigsaw-2").unwrap(),
+		scoring: ScoringKind::Woodoku,
+		undo_limit: Some(2),
+	};
+	let mut replay = Replay::new(rules, 7);
+	let mut game = replay.new_game();
+	replay.moves.push(ReplayMove::deal(&game).unwrap());
+	let mut boards = vec![*game.board(); 2];
+	let mut scores = vec![game.score(); 2];
+	for turn in 0..40 {
+		if game.lost() {
+			break;
+		}
+		if turn % 7 == 6 {
+			replay.moves.push(ReplayMove::Hint);
+			boards.push(*game.board());
+			scores.push(game.score());
+		}
+		let game_move = match turn % 7 {
+			3 => {
+				assert!(game.undo());
+				ReplayMove::Undo
+			}
+			4 => {
+				assert!(game.redo());
+				ReplayMove::Redo
+			}
+			_ => {
+				let (index, mino, position) = (0..BATCH_SIZE)
+					.find_map(|index| {
+						let mino = game.mino(index)?;
+						let position = game.board().legal_placements(mino).next()?;
+						Some((index, mino, position))
+					})
+					.unwrap();
+				game.play(index, position).unwrap();
+				ReplayMove::Place {
+					index,
+					mino,
+					position,
+				}
+			}
+		};
+		replay.moves.push(game_move);
+		boards.push(*game.board());
+		scores.push(game.score());
+		if let Some(deal) =
+			ReplayMove::deal(&game).filter(|_| matches!(game_move, ReplayMove::Place { .. }))
+		{
+			replay.moves.push(deal);
+			boards.push(*game.board());
+			scores.push(game.score());
+		}
+	}
+	replay.score = Some(game.score());
+	(replay, game, boards, scores)
+}
+
+#[test]
+fn test_replay_round_trip() {
+	let (replay, _, boards, scores) = record_test_game();
+	assert!(replay.moves.len() > 10);
+
+	let text = replay.to_string();
+	assert!(text.starts_with("tetroku-replay 1\nseed 7\ngenerator bag 2 orientations\nsurvivable\n"));
+	let parsed: Replay = text.parse().unwrap();
+	assert_eq!(parsed, replay);
+
+	let games = parsed.play_back().unwrap();
+	assert!(games.iter().map(|game| *game.board()).eq(boards));
+	assert!(games.iter().map(Game::score).eq(scores));
+	assert!(games
+		.iter()
+		.all(|game| !game.can_undo() && !game.can_redo()));
+}
+
+#[test]
+fn test_replay_verify() {
+	let (replay, game, _, _) = record_test_game();
+	assert_eq!(
+		replay.verify().unwrap(),
+		Verification {
+			score: game.score(),
+			lost: game.lost(),
+			placements: replay
+				.moves
+				.iter()
+				.filter(|game_move| matches!(game_move, ReplayMove::Place { .. }))
+				.count(),
+			undos: 6,
+			hints: 5,
+		}
+	);
+	let mut inflated = replay.clone();
+	inflated.score = Some(game.score() + 1);
+	assert!(matches!(
+		inflated.verify(),
+		Err(ReplayError::WrongScore { claimed, actual }) if actual == game.score() && claimed == actual + 1
+	));
+
+	let mut tampered = replay.clone();
+	let ReplayMove::Place { mino, .. } = &mut tampered.moves[1] else {
+		unreachable!()
+	};
+	*mino = "#####".parse().unwrap();
+	assert!(matches!(
+		tampered.verify(),
+		Err(ReplayError::WrongMino { number: 2, .. })
+	));
+	let mut tampered = replay.clone();
+	let ReplayMove::Place { position, .. } = &mut tampered.moves[2] else {
+		unreachable!()
+	};
+	*position = (100, 100);
+	assert!(matches!(
+		tampered.verify(),
+		Err(ReplayError::Play { number: 3, .. })
+	));
+	// Coordinates near the limits of `Coordinate` are rejected rather than overflowing,
+	// including with minos whose top rows are empty padding, which are skipped before the bounds check.
+	let (mut standard, game, index) = (0..)
+		.find_map(|seed| {
+			let replay = Replay::new(Rules::default(), seed);
+			let game = replay.new_game();
+			let index = (0..BATCH_SIZE).find(|&index| game.mino(index).unwrap().min_point().1 >= 2)?;
+			Some((replay, game, index))
+		})
+		.unwrap();
+	standard.moves.push(ReplayMove::deal(&game).unwrap());
+	for position in [(0, 126), (126, 0), (127, 127), (-128, -128), (-128, 127)] {
+		let mut tampered = standard.clone();
+		tampered.moves.push(ReplayMove::Place {
+			index,
+			mino: game.mino(index).unwrap(),
+			position,
+		});
+		assert!(matches!(
+			tampered.verify(),
+			Err(ReplayError::Play {
+				number: 2,
+				error: PlayError::Place(PlaceError::OutOfBounds)
+			})
+		));
+	}
+
+	// Every dealt batch is checked, including minos that are never placed.
+	let mut tampered = replay.clone();
+	let ReplayMove::Deal { minos } = &mut tampered.moves[0] else {
+		unreachable!()
+	};
+	minos[2] = "#####".parse().unwrap();
+	assert!(matches!(
+		tampered.verify(),
+		Err(ReplayError::WrongBatch { number: 1, .. })
+	));
+	let last_deal = replay
+		.moves
+		.iter()
+		.rposition(|game_move| matches!(game_move, ReplayMove::Deal { .. }))
+		.unwrap();
+	let mut tampered = replay.clone();
+	tampered.moves.remove(last_deal);
+	assert!(matches!(
+		tampered.verify(),
+		Err(ReplayError::MissingDeal { number }) if number == last_deal + 1
+	));
+	let mut tampered = replay;
+	tampered.moves.insert(1, tampered.moves[0]);
+	assert!(matches!(
+		tampered.verify(),
+		Err(ReplayError::UnexpectedDeal { number: 2 })
+	));
+}
+
+#[test]
+fn test_replay_parse_errors() {
+	let valid = Replay::new(Rules::default(), 1).to_string();
+	assert_eq!(
+		valid
+			.replace("tetroku-replay 1", "tetroku-replay 2")
+			.parse::<Replay>(),
+		Err(ReplayParseError::UnsupportedVersion { version: 2 })
+	);
+	assert_eq!(
+		valid.replace("seed 1\n", "").parse::<Replay>(),
+		Err(ReplayParseError::MissingField { field: "seed" })
+	);
+	assert_eq!(
+		valid.replace("seed 1", "seed one").parse::<Replay>(),
+		Err(ReplayParseError::InvalidValue {
+			line: 2,
+			field: "seed".to_owned()
+		})
+	);
+	assert_eq!(
+		valid
+			.replace("generator uniform", "generator hard 7")
+			.parse::<Replay>(),
+		Err(ReplayParseError::InvalidValue {
+			line: 3,
+			field: "generator".to_owned()
+		})
+	);
+	assert_eq!(
+		format!("{valid}\tplace 0 1\n").parse::<Replay>(),
+		Err(ReplayParseError::InvalidMove {
+			line: valid.lines().count() + 1
+		})
+	);
+	assert_eq!(
+		format!("{valid}seed 2\n").parse::<Replay>(),
+		Err(ReplayParseError::DuplicateField {
+			line: valid.lines().count() + 1,
+			field: "seed".to_owned()
+		})
+	);
+	assert_eq!(
+		"seed 1".parse::<Replay>(),
+		Err(ReplayParseError::MissingHeader)
+	);
+}
@@ -0,0 +1,90 @@
+/// A small seedable pseudo-random number generator (`SplitMix64`).
+///
+/// Unlike the generators provided by `rand`, the output for a given seed is part of this crate's stability guarantees,
+/// so games generated from a seed can be reproduced across versions and platforms.
+#[allow(missing_copy_implementations /* copying by accident would repeat the output */)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rng {
+	state: u64,
+}
+
+impl Rng {
+	#[inline]
+	#[must_use]
+	pub fn new(seed: u64) -> Self {
+		Self { state: seed }
+	}
+
+	pub fn next_u64(&mut self) -> u64 {
+		self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+		let mut z = self.state;
+		z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+		z ^ (z >> 31)
+	}
+
+	/// Returns a uniformly distributed value in `0..bound`.
+	///
+	/// # Panics
+	///
+	/// Panics if `bound` is zero.
+	pub fn below(&mut self, bound: usize) -> usize {
+		let bound = u64::try_from(bound).unwrap_or_else(|_| unreachable!());
+		usize::try_from(self.below_u64(bound)).unwrap_or_else(|_| unreachable!())
+	}
+
+	/// Returns a uniformly distributed value in `0..bound`.
+	///
+	/// # Panics
+	///
+	/// Panics if `bound` is zero.
+	pub fn below_u64(&mut self, bound: u64) -> u64 {
+		assert!(bound > 0, "`bound` must be positive");
+		// Lemire's nearly divisionless method.
+		let threshold = bound.wrapping_neg() % bound;
+		loop {
+			let product = u128::from(self.next_u64()) * u128::from(bound);
+			#[allow(clippy::cast_possible_truncation /* intentional */)]
+			let low = product as u64;
+			if low >= threshold {
+				#[allow(clippy::cast_possible_truncation /* `product < bound << 64` */)]
+				return (product >> 64) as u64;
+			}
+		}
+	}
+
+	pub fn bool(&mut self) -> bool {
+		self.next_u64() >> 63 == 1
+	}
+
+	/// Shuffle `items` uniformly using the Fisher-Yates algorithm.
+	pub fn shuffle<T>(&mut self, items: &mut [T]) {
+		for i in (1..items.len()).rev() {
+			items.swap(i, self.below(i + 1));
+		}
+	}
+}
+
+#[test]
+fn test_rng_is_stable() {
+	let mut rng = Rng::new(1_234_567);
+	assert_eq!(
+		[rng.next_u64(), rng.next_u64(), rng.next_u64()],
+		[
+			6_457_827_717_110_365_317,
+			3_203_168_211_198_807_973,
+			9_817_491_932_198_370_423,
+		],
+	);
+}
+
+#[test]
+fn test_rng_below() {
+	let mut rng = Rng::new(0);
+	let mut seen = [false; 7];
+	for _ in 0..1000 {
+		seen[rng.below(7)] = true;
+	}
+	assert!(seen.iter().all(|&seen| seen));
+}
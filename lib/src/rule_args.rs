@@ -0,0 +1,345 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::generator::{GeneratorKind, HardGenerator};
+use crate::layout::{Layout, LayoutError};
+use crate::mino_set::{MinoSet, MinoSetParseError};
+use crate::rules::Rules;
+use crate::scoring::ScoringKind;
+use crate::util::Coordinate;
+
+/// Collects the command-line options that choose the rules of a game, which the frontends share.
+///
+/// Feed it every argument with `RuleArgs::parse`, then turn it into `Rules` with `RuleArgs::into_rules`.
+/// The undo limit is left to the frontend, since not every frontend lets players undo.
+#[derive(Debug, Clone, Default)]
+pub struct RuleArgs {
+	generator: Option<String>,
+	bag_copies: Option<usize>,
+	bag_orientations: bool,
+	hardness: Option<u32>,
+	minos: Option<MinoSet>,
+	survivable: bool,
+	scoring: ScoringKind,
+	board_size: Option<(Coordinate, Coordinate)>,
+	box_size: Option<(Coordinate, Coordinate)>,
+	layout: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum RuleArgsError {
+	/// The option takes a value but was the last argument.
+	MissingValue { option: String },
+	InvalidValue {
+		option: String,
+		value: String,
+		/// What the value should look like.
+		expected: &'static str,
+	},
+	/// The option only makes sense together with `requirement`.
+	Requires {
+		option: &'static str,
+		requirement: &'static str,
+	},
+	Conflict {
+		option: &'static str,
+		other: &'static str,
+	},
+	/// The file given to `--minos` couldn't be read.
+	ReadFile { path: String, error: std::io::Error },
+	/// The value of `--layout` is neither the name of a built-in layout nor a readable file.
+	UnknownLayout { path: String, error: std::io::Error },
+	InvalidMinos {
+		path: String,
+		error: MinoSetParseError,
+	},
+	/// The region map given to `--layout`, or the sizes given to `--board` and `--box`, are invalid.
+	InvalidLayout {
+		path: Option<String>,
+		error: LayoutError,
+	},
+}
+
+impl Display for RuleArgsError {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::MissingValue { option } => write!(formatter, "missing value for {option}"),
+			Self::InvalidValue {
+				option,
+				value,
+				expected,
+			} => write!(
+				formatter,
+				"invalid value {value:?} for {option}: expected {expected}"
+			),
+			Self::Requires {
+				option,
+				requirement,
+			} => write!(formatter, "{option} requires {requirement}"),
+			Self::Conflict { option, other } => write!(formatter, "{option} conflicts with {other}"),
+			Self::ReadFile { path, error } => write!(formatter, "could not read {path:?}: {error}"),
+			Self::UnknownLayout { path, error } => write!(
+				formatter,
+				"{path:?} is neither a built-in layout nor a readable file: {error}"
+			),
+			Self::InvalidMinos { path, error } => {
+				write!(formatter, "invalid mino set in {path:?}: {error}")
+			}
+			Self::InvalidLayout {
+				path: Some(path),
+				error,
+			} => write!(formatter, "invalid region map in {path:?}: {error}"),
+			Self::InvalidLayout { path: None, error } => write!(formatter, "invalid layout: {error}"),
+		}
+	}
+}
+
+impl std::error::Error for RuleArgsError {}
+
+fn read_file(path: &str) -> Result<String, RuleArgsError> {
+	std::fs::read_to_string(path).map_err(|error| RuleArgsError::ReadFile {
+		path: path.to_owned(),
+		error,
+	})
+}
+
+impl RuleArgs {
+	/// The lines of the usage message that describe the options accepted by `RuleArgs::parse`.
+	pub const USAGE: &'static str = "  --generator <GENERATOR>   How minos are generated: `uniform` (default), `bag`, or `hard`.
+  --bag-copies <COPIES>     Copies of each tile in a bag. Defaults to 1.
+  --bag-orientations        Also put every orientation of each tile in the bag.
+  --hardness <HARDNESS>     How strongly `hard` prefers awkward minos, from 0 to 6. Defaults to 2.
+  --minos <FILE>            Play with the minos defined in a mino set file instead of the built-in ones.
+  --survivable              Reroll batches until their minos can all be placed in some order.
+  --scoring <RULE>          How placements are scored: `classic` (default), `ten-ten`, or `woodoku`.
+  --board <WIDTH>x<HEIGHT>  Size of the board, at most 16x16. Defaults to 9x9.
+  --box <WIDTH>x<HEIGHT>    Size of the boxes, which must evenly divide the board. Defaults to 3x3.
+  --layout <LAYOUT>         A built-in layout (`standard`, `jigsaw-1`, `jigsaw-2`, or `jigsaw-3`),
+                            or a file containing a region map. Conflicts with `--board` and `--box`.";
+
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Handle `option` if it is one of the rule options, taking its value from `args` if it has one.
+	///
+	/// Returns `false` if `option` isn't a rule option, in which case `args` is left untouched.
+	///
+	/// # Errors
+	///
+	/// Fails if the value is missing or invalid, or if a file it names can't be read or parsed.
+	pub fn parse(
+		&mut self,
+		option: &str,
+		args: &mut impl Iterator<Item = String>,
+	) -> Result<bool, RuleArgsError> {
+		let mut value = || {
+			args.next().ok_or_else(|| RuleArgsError::MissingValue {
+				option: option.to_owned(),
+			})
+		};
+		let invalid = |value: String, expected| RuleArgsError::InvalidValue {
+			option: option.to_owned(),
+			value,
+			expected,
+		};
+
+		match option {
+			"--generator" => self.generator = Some(value()?),
+			"--bag-copies" => {
+				let copies = value()?;
+				match copies.parse() {
+					Ok(0) | Err(_) => return Err(invalid(copies, "a positive number")),
+					Ok(copies) => self.bag_copies = Some(copies),
+				}
+			}
+			"--bag-orientations" => self.bag_orientations = true,
+			"--hardness" => {
+				let hardness = value()?;
+				match hardness.parse() {
+					Ok(hardness) if hardness <= HardGenerator::MAX_HARDNESS => {
+						self.hardness = Some(hardness);
+					}
+					_ => return Err(invalid(hardness, "a number from 0 to 6")),
+				}
+			}
+			"--minos" => {
+				let path = value()?;
+				self.minos = Some(
+					read_file(&path)?
+						.parse()
+						.map_err(|error| RuleArgsError::InvalidMinos { path, error })?,
+				);
+			}
+			"--survivable" => self.survivable = true,
+			"--scoring" => {
+				let scoring = value()?;
+				self.scoring = scoring
+					.parse()
+					.map_err(|_| invalid(scoring, "`classic`, `ten-ten` or `woodoku`"))?;
+			}
+			"--board" | "--box" => {
+				let size = value()?;
+				let parsed = size
+					.split_once('x')
+					.and_then(|(width, height)| Some((width.parse().ok()?, height.parse().ok()?)))
+					.ok_or_else(|| invalid(size, "<WIDTH>x<HEIGHT>"))?;
+				if option == "--board" {
+					self.board_size = Some(parsed);
+				} else {
+					self.box_size = Some(parsed);
+				}
+			}
+			"--layout" => self.layout = Some(value()?),
+			_ => return Ok(false),
+		}
+		Ok(true)
+	}
+
+	fn generator_kind(&self) -> Result<GeneratorKind, RuleArgsError> {
+		let kind = match self.generator.as_deref() {
+			None | Some("uniform") => GeneratorKind::Uniform,
+			Some("bag") => GeneratorKind::Bag {
+				copies: self.bag_copies.unwrap_or(1),
+				orientations: self.bag_orientations,
+			},
+			Some("hard") => GeneratorKind::Hard {
+				hardness: self.hardness.unwrap_or(2),
+			},
+			Some(other) => {
+				return Err(RuleArgsError::InvalidValue {
+					option: "--generator".to_owned(),
+					value: other.to_owned(),
+					expected: "`uniform`, `bag` or `hard`",
+				})
+			}
+		};
+		if (self.bag_copies.is_some() || self.bag_orientations)
+			&& !matches!(kind, GeneratorKind::Bag { .. })
+		{
+			return Err(RuleArgsError::Requires {
+				option: "--bag-copies and --bag-orientations",
+				requirement: "--generator bag",
+			});
+		}
+		if self.hardness.is_some() && !matches!(kind, GeneratorKind::Hard { .. }) {
+			return Err(RuleArgsError::Requires {
+				option: "--hardness",
+				requirement: "--generator hard",
+			});
+		}
+		Ok(kind)
+	}
+
+	fn layout(&self) -> Result<&'static Layout, RuleArgsError> {
+		let Some(layout) = &self.layout else {
+			let (width, height) = self.board_size.unwrap_or((9, 9));
+			let (box_width, box_height) = self.box_size.unwrap_or((3, 3));
+			return Layout::new(width, height, box_width, box_height)
+				.map(Layout::intern)
+				.map_err(|error| RuleArgsError::InvalidLayout { path: None, error });
+		};
+		if self.board_size.is_some() || self.box_size.is_some() {
+			return Err(RuleArgsError::Conflict {
+				option: "--layout",
+				other: "--board and --box",
+			});
+		}
+		if let Some(builtin) = Layout::builtin(layout) {
+			return Ok(builtin);
+		}
+		std::fs::read_to_string(layout)
+			.map_err(|error| RuleArgsError::UnknownLayout {
+				path: layout.clone(),
+				error,
+			})?
+			.parse::<Layout>()
+			.map(Layout::intern)
+			.map_err(|error| RuleArgsError::InvalidLayout {
+				path: Some(layout.clone()),
+				error,
+			})
+	}
+
+	/// The rules chosen by the options, with unlimited undo.
+	///
+	/// # Errors
+	///
+	/// Fails if the options conflict or the layout is invalid.
+	pub fn into_rules(self) -> Result<Rules, RuleArgsError> {
+		Ok(Rules {
+			generator: self.generator_kind()?,
+			survivable: self.survivable,
+			layout: self.layout()?,
+			minos: self.minos.unwrap_or_else(MinoSet::builtin),
+			scoring: self.scoring,
+			undo_limit: None,
+		})
+	}
+}
+
+#[test]
+fn test_rule_args() {
+	let parse = |args: &[&str]| {
+		let mut rule_args = RuleArgs::new();
+		let mut args = args.iter().map(|&arg| arg.to_owned());
+		while let Some(arg) = args.next() {
+			assert!(rule_args.parse(&arg, &mut args)?, "{arg}");
+		}
+		rule_args.into_rules()
+	};
+
+	assert_eq!(parse(&[]).unwrap(), Rules::default());
+	let rules = parse(&[
+		"--generator",
+		"bag",
+		"--bag-copies",
+		"2",
+		"--survivable",
+		"--scoring",
+		"woodoku",
+		"--board",
+		"6x6",
+		"--box",
+		"2x3",
+	])
+	.unwrap();
+	assert_eq!(
+		rules.generator,
+		GeneratorKind::Bag {
+			copies: 2,
+			orientations: false
+		}
+	);
+	assert!(rules.survivable);
+	assert_eq!(rules.scoring, ScoringKind::Woodoku);
+	assert_eq!(rules.layout, &Layout::new(6, 6, 2, 3).unwrap());
+	assert_eq!(
+		parse(&["--layout", "jigsaw-1"]).unwrap().layout,
+		Layout::builtin("jigsaw-1").unwrap()
+	);
+
+	assert!(matches!(
+		parse(&["--bag-orientations"]),
+		Err(RuleArgsError::Requires { .. })
+	));
+	assert!(matches!(
+		parse(&["--hardness", "7"]),
+		Err(RuleArgsError::InvalidValue { .. })
+	));
+	assert!(matches!(
+		parse(&["--layout", "standard", "--box", "3x3"]),
+		Err(RuleArgsError::Conflict { .. })
+	));
+	assert!(matches!(
+		parse(&["--board", "5x5"]),
+		Err(RuleArgsError::InvalidLayout { path: None, .. })
+	));
+	assert!(matches!(
+		parse(&["--scoring"]),
+		Err(RuleArgsError::MissingValue { .. })
+	));
+	assert!(!RuleArgs::new()
+		.parse("--seed", &mut ["1".to_owned()].into_iter())
+		.unwrap());
+}
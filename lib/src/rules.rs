@@ -0,0 +1,65 @@
+use crate::board::Board;
+use crate::game::Game;
+use crate::generator::{AnyGenerator, GeneratorKind};
+use crate::layout::Layout;
+use crate::mino_set::MinoSet;
+use crate::scoring::ScoringKind;
+
+/// Everything that determines how a game plays out, other than the seed and the moves:
+/// the built-in generator and tiles, the layout, the scoring rule and the undo limit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+	pub generator: GeneratorKind,
+	/// Whether the generator is wrapped in `Survivable`.
+	pub survivable: bool,
+	pub minos: MinoSet,
+	pub layout: &'static Layout,
+	pub scoring: ScoringKind,
+	/// See `Game::with_undo_limit`.
+	pub undo_limit: Option<usize>,
+}
+
+/// The uniform generator with the built-in tiles on the standard layout, scored classically, with unlimited undo.
+impl Default for Rules {
+	fn default() -> Self {
+		Self {
+			generator: GeneratorKind::default(),
+			survivable: false,
+			minos: MinoSet::builtin(),
+			layout: Layout::standard(),
+			scoring: ScoringKind::default(),
+			undo_limit: None,
+		}
+	}
+}
+
+impl Rules {
+	/// Whether these are the default rules, apart from the undo limit, which is up to the player or the score board.
+	#[must_use]
+	pub fn is_standard(&self) -> bool {
+		*self
+			== Self {
+				undo_limit: self.undo_limit,
+				..Self::default()
+			}
+	}
+
+	/// The generator these rules deal minos with.
+	#[must_use]
+	pub fn generator(&self, seed: u64) -> AnyGenerator {
+		let generator = AnyGenerator::new(self.generator, seed).with_set(self.minos.clone());
+		if self.survivable {
+			generator.survivable()
+		} else {
+			generator
+		}
+	}
+
+	/// Start a game on an empty board. The same rules and seed always deal the same minos for the same moves.
+	#[must_use]
+	pub fn new_game(&self, seed: u64) -> Game<AnyGenerator, ScoringKind> {
+		Game::with_board(self.generator(seed), Board::with_layout(self.layout))
+			.with_scoring(self.scoring)
+			.with_undo_limit(self.undo_limit)
+	}
+}
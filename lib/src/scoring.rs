@@ -0,0 +1,192 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use crate::board::WouldRemove;
+use crate::mino::Mino;
+
+/// Decides how many points a placement is worth.
+pub trait ScoringRule {
+	/// The points for placing `mino`, which cleared the regions in `cleared`.
+	///
+	/// `streak` is the number of placements in a row before this one that cleared at least one region.
+	fn points(&self, mino: Mino, cleared: &[WouldRemove], streak: u32) -> u32;
+}
+
+impl<S: ScoringRule + ?Sized> ScoringRule for &S {
+	fn points(&self, mino: Mino, cleared: &[WouldRemove], streak: u32) -> u32 {
+		(**self).points(mino, cleared, streak)
+	}
+}
+
+impl<S: ScoringRule + ?Sized> ScoringRule for Box<S> {
+	fn points(&self, mino: Mino, cleared: &[WouldRemove], streak: u32) -> u32 {
+		(**self).points(mino, cleared, streak)
+	}
+}
+
+fn num_squares(mino: Mino) -> u32 {
+	u32::try_from(mino.num_squares()).unwrap_or_else(|_| unreachable!())
+}
+
+fn num_cleared(cleared: &[WouldRemove]) -> u32 {
+	u32::try_from(cleared.len()).unwrap_or_else(|_| unreachable!())
+}
+
+/// One point per square placed, plus 3 points per region cleared and another 3 for each region beyond the first.
+///
+/// The points for clearing are multiplied by the streak bonus: one more than the streak, up to `MAX_STREAK_MULTIPLIER`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClassicScoring;
+
+impl ClassicScoring {
+	pub const MAX_STREAK_MULTIPLIER: u32 = 5;
+
+	/// The multiplier applied to the points for clearing when the streak is `streak`.
+	#[inline]
+	#[must_use]
+	pub fn streak_multiplier(streak: u32) -> u32 {
+		streak.saturating_add(1).min(Self::MAX_STREAK_MULTIPLIER)
+	}
+}
+
+impl ScoringRule for ClassicScoring {
+	fn points(&self, mino: Mino, cleared: &[WouldRemove], streak: u32) -> u32 {
+		let cleared = num_cleared(cleared);
+		let clear_points = 3 * cleared + 3 * cleared.saturating_sub(1);
+		num_squares(mino) + clear_points * Self::streak_multiplier(streak)
+	}
+}
+
+/// Scoring in the style of 1010!: one point per square placed,
+/// plus 10 points for the first region cleared at once, 20 for the second, 30 for the third, and so on.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TenTenScoring;
+
+impl ScoringRule for TenTenScoring {
+	fn points(&self, mino: Mino, cleared: &[WouldRemove], _streak: u32) -> u32 {
+		let cleared = num_cleared(cleared);
+		num_squares(mino) + 5 * cleared * (cleared + 1)
+	}
+}
+
+/// Scoring in the style of Woodoku: one point per square placed and 18 per region cleared,
+/// with a combo bonus of 10 for each region beyond the first
+/// and a streak bonus of 10 for each consecutive placement before this one that also cleared something.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WoodokuScoring;
+
+impl ScoringRule for WoodokuScoring {
+	fn points(&self, mino: Mino, cleared: &[WouldRemove], streak: u32) -> u32 {
+		let cleared = num_cleared(cleared);
+		if cleared == 0 {
+			return num_squares(mino);
+		}
+		num_squares(mino) + 18 * cleared + 10 * (cleared - 1) + 10 * streak
+	}
+}
+
+/// One of the built-in scoring rules, for example chosen from a command-line option.
+///
+/// `Display` writes the name of the rule, like `ten-ten`, which `FromStr` accepts.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(rename_all = "snake_case")
+)]
+pub enum ScoringKind {
+	/// See `ClassicScoring`.
+	#[default]
+	Classic,
+	/// See `TenTenScoring`.
+	TenTen,
+	/// See `WoodokuScoring`.
+	Woodoku,
+}
+
+impl ScoringKind {
+	pub const ALL: [Self; 3] = [Self::Classic, Self::TenTen, Self::Woodoku];
+
+	/// `classic`, `ten-ten` or `woodoku`.
+	#[must_use]
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::Classic => "classic",
+			Self::TenTen => "ten-ten",
+			Self::Woodoku => "woodoku",
+		}
+	}
+}
+
+impl Display for ScoringKind {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		formatter.write_str(self.name())
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScoringKindParseError;
+
+impl Display for ScoringKindParseError {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		formatter.write_str("unknown scoring rule; expected `classic`, `ten-ten` or `woodoku`")
+	}
+}
+
+impl std::error::Error for ScoringKindParseError {}
+
+impl FromStr for ScoringKind {
+	type Err = ScoringKindParseError;
+
+	fn from_str(name: &str) -> Result<Self, Self::Err> {
+		Self::ALL
+			.into_iter()
+			.find(|kind| kind.name() == name)
+			.ok_or(ScoringKindParseError)
+	}
+}
+
+impl ScoringRule for ScoringKind {
+	fn points(&self, mino: Mino, cleared: &[WouldRemove], streak: u32) -> u32 {
+		match self {
+			Self::Classic => ClassicScoring.points(mino, cleared, streak),
+			Self::TenTen => TenTenScoring.points(mino, cleared, streak),
+			Self::Woodoku => WoodokuScoring.points(mino, cleared, streak),
+		}
+	}
+}
+
+#[test]
+fn test_scoring_rules() {
+	let mino: Mino = "##\n#.".parse().unwrap();
+	let rows =
+		|count| -> Vec<WouldRemove> { (0..count).map(|y| WouldRemove::Horizontal { y }).collect() };
+
+	assert_eq!(ClassicScoring.points(mino, &rows(0), 0), 3);
+	assert_eq!(ClassicScoring.points(mino, &rows(1), 0), 3 + 3);
+	assert_eq!(ClassicScoring.points(mino, &rows(3), 0), 3 + 9 + 6);
+	assert_eq!(ClassicScoring.points(mino, &rows(1), 2), 3 + 3 * 3);
+	assert_eq!(ClassicScoring.points(mino, &rows(0), 2), 3);
+	assert_eq!(ClassicScoring.points(mino, &rows(1), 100), 3 + 3 * 5);
+
+	assert_eq!(TenTenScoring.points(mino, &rows(0), 0), 3);
+	assert_eq!(TenTenScoring.points(mino, &rows(1), 0), 3 + 10);
+	assert_eq!(TenTenScoring.points(mino, &rows(3), 0), 3 + 60);
+
+	assert_eq!(WoodokuScoring.points(mino, &rows(0), 4), 3);
+	assert_eq!(WoodokuScoring.points(mino, &rows(1), 0), 3 + 18);
+	assert_eq!(WoodokuScoring.points(mino, &rows(2), 2), 3 + 36 + 10 + 20);
+
+	assert_eq!(
+		ScoringKind::TenTen.points(mino, &rows(2), 0),
+		TenTenScoring.points(mino, &rows(2), 0)
+	);
+
+	for kind in ScoringKind::ALL {
+		assert_eq!(kind.name().parse(), Ok(kind));
+	}
+	assert_eq!("tetris".parse::<ScoringKind>(), Err(ScoringKindParseError));
+}
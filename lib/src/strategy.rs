@@ -0,0 +1,328 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use crate::board::Board;
+#[cfg(test)]
+use crate::board::Position;
+#[cfg(test)]
+use crate::game::Game;
+use crate::game::{Selection, BATCH_SIZE};
+#[cfg(test)]
+use crate::generator::{AnyGenerator, GeneratorKind, UniformGenerator};
+use crate::mino::Mino;
+use crate::rng::Rng;
+use crate::scoring::ScoringRule;
+use crate::util::Coordinate;
+
+/// What a strategy gets to see of a game before choosing a placement, as returned by `Game::turn`.
+#[derive(Clone, Copy)]
+pub struct Turn<'a> {
+	pub board: &'a Board,
+	/// The minos of the current batch, with `None` for those that have already been placed.
+	pub minos: [Option<Mino>; BATCH_SIZE],
+	pub score: u32,
+	/// See `Game::streak`.
+	pub streak: u32,
+	pub scoring: &'a dyn ScoringRule,
+}
+
+impl Turn<'_> {
+	/// Every legal placement of the minos left in the batch, as the mino and where to place it.
+	pub fn legal_placements(&self) -> impl Iterator<Item = (Mino, Selection)> + '_ {
+		(0..BATCH_SIZE)
+			.filter_map(|index| Some((index, self.minos[index]?)))
+			.flat_map(|(index, mino)| {
+				self
+					.board
+					.legal_placements(mino)
+					.map(move |position| (mino, Selection { index, position }))
+			})
+	}
+
+	/// The board after placing `mino` at `position` and the points it would be worth.
+	///
+	/// # Panics
+	///
+	/// Panics if the placement isn't legal.
+	#[must_use]
+	pub fn outcome(&self, mino: Mino, position: (Coordinate, Coordinate)) -> (Board, u32) {
+		let (board, cleared) = self
+			.board
+			.place_and_clear(mino, position)
+			.expect("the placement must be legal");
+		let cleared: Vec<_> = cleared.iter().collect();
+		(board, self.scoring.points(mino, &cleared, self.streak))
+	}
+}
+
+/// A bot that plays the game by choosing placements.
+///
+/// Use it with `Game::turn` and `Game::play`:
+///
+/// ```
+/// # use tetroku_lib::{Game, GreedyStrategy, Strategy, UniformGenerator};
+/// let mut game = Game::new(UniformGenerator::new(1));
+/// let mut strategy = GreedyStrategy;
+/// let selection = strategy.choose(game.turn()).unwrap();
+/// game.play(selection.index, selection.position).unwrap();
+/// ```
+pub trait Strategy {
+	/// The index of a mino in the batch and where to place it, or `None` if none of the remaining minos can be placed.
+	///
+	/// The placement must be legal.
+	fn choose(&mut self, turn: Turn<'_>) -> Option<Selection>;
+}
+
+impl<S: Strategy + ?Sized> Strategy for &mut S {
+	fn choose(&mut self, turn: Turn<'_>) -> Option<Selection> {
+		(**self).choose(turn)
+	}
+}
+
+impl<S: Strategy + ?Sized> Strategy for Box<S> {
+	fn choose(&mut self, turn: Turn<'_>) -> Option<Selection> {
+		(**self).choose(turn)
+	}
+}
+
+/// Picks uniformly from all legal placements.
+///
+/// The same seed always makes the same choices for the same turns.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RandomStrategy {
+	rng: Rng,
+}
+
+impl RandomStrategy {
+	#[must_use]
+	pub fn new(seed: u64) -> Self {
+		Self {
+			rng: Rng::new(seed),
+		}
+	}
+}
+
+impl Strategy for RandomStrategy {
+	fn choose(&mut self, turn: Turn<'_>) -> Option<Selection> {
+		let placements: Vec<Selection> = turn
+			.legal_placements()
+			.map(|(_, selection)| selection)
+			.collect();
+		if placements.is_empty() {
+			return None;
+		}
+		Some(placements[self.rng.below(placements.len())])
+	}
+}
+
+/// Picks the placement worth the most points right away, without regard for what comes after.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GreedyStrategy;
+
+impl Strategy for GreedyStrategy {
+	fn choose(&mut self, turn: Turn<'_>) -> Option<Selection> {
+		turn
+			.legal_placements()
+			.max_by_key(|&(mino, selection)| turn.outcome(mino, selection.position).1)
+			.map(|(_, selection)| selection)
+	}
+}
+
+/// Picks the placement that leaves the board most open: with many empty cells,
+/// few empty cells enclosed on all sides, and short borders between empty and occupied cells,
+/// while making sure that the rest of the batch can still be placed. Points break ties.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HeuristicStrategy;
+
+impl HeuristicStrategy {
+	/// Higher is better.
+	fn evaluate(board: &Board, rest_of_batch: impl Iterator<Item = Mino>) -> i64 {
+		let layout = board.layout();
+		let empty = |x, y| {
+			layout
+				.position(x, y)
+				.is_some_and(|position| !board.occupied(position))
+		};
+
+		let mut value = 0;
+		for position in layout
+			.positions()
+			.filter(|&position| !board.occupied(position))
+		{
+			let (x, y) = position.to_xy();
+			let neighbors = [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)];
+			let open_neighbors = neighbors.iter().filter(|&&(x, y)| empty(x, y)).count();
+			let blocked_neighbors = neighbors
+				.iter()
+				.filter(|&&(x, y)| layout.position(x, y).is_some() && !empty(x, y))
+				.count();
+			value += 3;
+			if open_neighbors == 0 {
+				value -= 10;
+			}
+			value -= i64::try_from(blocked_neighbors).unwrap_or_else(|_| unreachable!());
+		}
+
+		let stuck = rest_of_batch
+			.filter(|&mino| !board.can_place_anywhere(mino))
+			.count();
+		value - 1000 * i64::try_from(stuck).unwrap_or_else(|_| unreachable!())
+	}
+}
+
+impl Strategy for HeuristicStrategy {
+	fn choose(&mut self, turn: Turn<'_>) -> Option<Selection> {
+		turn
+			.legal_placements()
+			.max_by_key(|&(mino, selection)| {
+				let (board, points) = turn.outcome(mino, selection.position);
+				let rest_of_batch = (0..BATCH_SIZE)
+					.filter(|&index| index != selection.index)
+					.filter_map(|index| turn.minos[index]);
+				(Self::evaluate(&board, rest_of_batch), points)
+			})
+			.map(|(_, selection)| selection)
+	}
+}
+
+/// One of the built-in strategies, for example chosen from a command-line option.
+///
+/// `Display` writes the name of the strategy, like `greedy`, which `FromStr` accepts.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(rename_all = "snake_case")
+)]
+pub enum StrategyKind {
+	/// See `RandomStrategy`.
+	Random,
+	/// See `GreedyStrategy`.
+	Greedy,
+	/// See `HeuristicStrategy`.
+	#[default]
+	Heuristic,
+}
+
+impl StrategyKind {
+	pub const ALL: [Self; 3] = [Self::Random, Self::Greedy, Self::Heuristic];
+
+	/// `random`, `greedy` or `heuristic`.
+	#[must_use]
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::Random => "random",
+			Self::Greedy => "greedy",
+			Self::Heuristic => "heuristic",
+		}
+	}
+}
+
+impl Display for StrategyKind {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		formatter.write_str(self.name())
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StrategyKindParseError;
+
+impl Display for StrategyKindParseError {
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		formatter.write_str("unknown strategy; expected `random`, `greedy` or `heuristic`")
+	}
+}
+
+impl std::error::Error for StrategyKindParseError {}
+
+impl FromStr for StrategyKind {
+	type Err = StrategyKindParseError;
+
+	fn from_str(name: &str) -> Result<Self, Self::Err> {
+		Self::ALL
+			.into_iter()
+			.find(|kind| kind.name() == name)
+			.ok_or(StrategyKindParseError)
+	}
+}
+
+/// A built-in strategy chosen at runtime, for example from a command-line option.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+	feature = "serde",
+	derive(serde::Serialize, serde::Deserialize),
+	serde(rename_all = "snake_case")
+)]
+pub enum AnyStrategy {
+	Random(RandomStrategy),
+	Greedy(GreedyStrategy),
+	Heuristic(HeuristicStrategy),
+}
+
+impl AnyStrategy {
+	/// `seed` is only used by `RandomStrategy`.
+	#[must_use]
+	pub fn new(kind: StrategyKind, seed: u64) -> Self {
+		match kind {
+			StrategyKind::Random => Self::Random(RandomStrategy::new(seed)),
+			StrategyKind::Greedy => Self::Greedy(GreedyStrategy),
+			StrategyKind::Heuristic => Self::Heuristic(HeuristicStrategy),
+		}
+	}
+}
+
+impl Strategy for AnyStrategy {
+	fn choose(&mut self, turn: Turn<'_>) -> Option<Selection> {
+		match self {
+			Self::Random(strategy) => strategy.choose(turn),
+			Self::Greedy(strategy) => strategy.choose(turn),
+			Self::Heuristic(strategy) => strategy.choose(turn),
+		}
+	}
+}
+
+#[test]
+fn test_strategies_play_legally() {
+	for kind in StrategyKind::ALL {
+		assert_eq!(kind.name().parse(), Ok(kind));
+		let mut strategy = AnyStrategy::new(kind, 1);
+		let mut game = Game::new(AnyGenerator::new(GeneratorKind::Uniform, 2));
+		for _ in 0..100 {
+			let Some(selection) = strategy.choose(game.turn()) else {
+				assert!(game.lost(), "the {kind} strategy gave up early");
+				break;
+			};
+			game.play(selection.index, selection.position).unwrap();
+		}
+	}
+	assert_eq!(
+		"minimax".parse::<StrategyKind>(),
+		Err(StrategyKindParseError)
+	);
+}
+
+#[test]
+fn test_strategies_complete_rows() {
+	let mut board = Board::new();
+	for x in 0..6 {
+		board.set(Position::new_unchecked(x, 0), true);
+	}
+	let game = Game::with_board(UniformGenerator::new(0), board);
+	let turn = Turn {
+		minos: [
+			Some("#".parse().unwrap()),
+			Some("###".parse().unwrap()),
+			None,
+		],
+		..game.turn()
+	};
+	let clear_row = Some(Selection {
+		index: 1,
+		position: (6, 0),
+	});
+	assert_eq!(GreedyStrategy.choose(turn), clear_row);
+	assert_eq!(HeuristicStrategy.choose(turn), clear_row);
+}
@@ -2,10 +2,6 @@ use std::fmt::{self, Debug, Formatter};
 
 pub type Coordinate = i8;
 
-pub const fn min_bytes_for_bits(bits: usize) -> usize {
-	(bits + 7) / 8
-}
-
 pub fn grid_fmt<I: Debug, F: Fn(usize, usize) -> I>(
 	formatter: &mut Formatter<'_>,
 	name: &str,
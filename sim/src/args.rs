@@ -0,0 +1,108 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context as _};
+use tetroku_lib::{RuleArgs, Rules, StrategyKind};
+
+const USAGE: &str = "\
+Usage: tetroku-sim [OPTIONS]
+
+Plays games without a terminal and prints statistics about them.
+
+Options:
+  --games <COUNT>           Number of games to play. Defaults to 100.
+  --seed <SEED>             Seed of the first game; each following game uses the next seed. Defaults to 0.
+  --strategy <STRATEGY>     How placements are chosen: `random`, `greedy`, or `heuristic` (default).
+  --max-placements <COUNT>  Stop games that haven't been lost after COUNT placements. Defaults to 10000.
+  --format <FORMAT>         `text` (default) or `json`.
+  -h, --help                Print this help.
+
+Rule options:";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+	Text,
+	Json,
+}
+
+#[derive(Debug, Clone)]
+pub struct Args {
+	pub games: u64,
+	pub seed: u64,
+	pub strategy: StrategyKind,
+	pub max_placements: usize,
+	pub format: Format,
+	pub rules: Rules,
+}
+
+impl Default for Args {
+	fn default() -> Self {
+		Self {
+			games: 100,
+			seed: 0,
+			strategy: StrategyKind::default(),
+			max_placements: 10_000,
+			format: Format::Text,
+			rules: Rules {
+				// Bots never undo, so don't keep any history.
+				undo_limit: Some(0),
+				..Rules::default()
+			},
+		}
+	}
+}
+
+fn parse_value<T: FromStr>(name: &str, value: &str) -> anyhow::Result<T>
+where
+	T::Err: std::error::Error + Send + Sync + 'static,
+{
+	value
+		.parse()
+		.with_context(|| format!("invalid value {value:?} for {name}"))
+}
+
+impl Args {
+	/// Parse the process's command-line arguments, printing the usage and exiting if `--help` is passed.
+	pub fn parse() -> anyhow::Result<Self> {
+		let mut ret = Self::default();
+
+		let mut rule_args = RuleArgs::new();
+
+		let mut args = std::env::args().skip(1);
+		while let Some(arg) = args.next() {
+			if rule_args.parse(&arg, &mut args)? {
+				continue;
+			}
+			let mut value = || {
+				args
+					.next()
+					.ok_or_else(|| anyhow!("missing value for {arg}"))
+			};
+
+			match arg.as_str() {
+				"--games" => ret.games = parse_value(&arg, &value()?)?,
+				"--seed" => ret.seed = parse_value(&arg, &value()?)?,
+				"--strategy" => ret.strategy = parse_value(&arg, &value()?)?,
+				"--max-placements" => ret.max_placements = parse_value(&arg, &value()?)?,
+				"--format" => {
+					ret.format = match value()?.as_str() {
+						"text" => Format::Text,
+						"json" => Format::Json,
+						other => bail!("unknown format {other:?}"),
+					};
+				}
+				"-h" | "--help" => {
+					println!("{USAGE}\n{}", RuleArgs::USAGE);
+					std::process::exit(0);
+				}
+				_ => bail!("unknown argument {arg:?}\n\n{USAGE}\n{}", RuleArgs::USAGE),
+			}
+		}
+
+		ret.rules = Rules {
+			undo_limit: ret.rules.undo_limit,
+			..rule_args.into_rules()?
+		};
+
+		Ok(ret)
+	}
+}
@@ -0,0 +1,84 @@
+#![deny(
+	absolute_paths_not_starting_with_crate,
+	keyword_idents,
+	macro_use_extern_crate,
+	meta_variable_misuse,
+	missing_abi,
+	missing_copy_implementations,
+	non_ascii_idents,
+	nonstandard_style,
+	noop_method_call,
+	rust_2018_idioms,
+	unused_qualifications
+)]
+#![warn(clippy::pedantic)]
+#![allow(clippy::module_name_repetitions)]
+#![forbid(unsafe_code)]
+
+use tetroku_lib::{AnyStrategy, Mino, MinoSet, Strategy as _};
+
+use crate::args::{Args, Format};
+use crate::stats::{GameStats, Summary};
+
+mod args;
+mod stats;
+
+/// The name of the piece in `set` that `mino` is an orientation of.
+fn mino_name(set: &MinoSet, mino: Mino) -> String {
+	let canonical = mino.canonical();
+	set
+		.iter()
+		.find(|&(_, piece)| piece.canonical() == canonical)
+		.map_or_else(
+			|| mino.to_string().replace('\n', "/"),
+			|(name, _)| name.to_owned(),
+		)
+}
+
+fn play(args: &Args, seed: u64) -> GameStats {
+	let mut game = args.rules.new_game(seed);
+	// Flip the bits so that the strategy doesn't draw the same numbers as the generator.
+	let mut strategy = AnyStrategy::new(args.strategy, !seed);
+	let mut stats = GameStats::default();
+	while stats.placements < args.max_placements {
+		let Some(selection) = strategy.choose(game.turn()) else {
+			break;
+		};
+		let outcome = game
+			.play(selection.index, selection.position)
+			.expect("strategies only choose legal placements");
+		stats.record(&outcome);
+	}
+	if let Some(generator) = game.generator().as_survivable() {
+		stats.rerolls = generator.total_rerolls();
+		stats.fallbacks = generator.fallbacks();
+	}
+	if game.lost() {
+		let mut stuck: Vec<String> = game
+			.minos()
+			.into_iter()
+			.flatten()
+			.map(|mino| mino_name(&args.rules.minos, mino))
+			.collect();
+		stuck.sort();
+		stuck.dedup();
+		stats.stuck = Some(stuck);
+	}
+	stats
+}
+
+fn main() -> anyhow::Result<()> {
+	let args = Args::parse()?;
+
+	let games: Vec<GameStats> = (0..args.games)
+		.map(|game| play(&args, args.seed.wrapping_add(game)))
+		.collect();
+	let summary = Summary::new(&games, args.seed, args.strategy, &args.rules);
+
+	match args.format {
+		Format::Text => print!("{summary}"),
+		Format::Json => println!("{}", serde_json::to_string_pretty(&summary)?),
+	}
+
+	Ok(())
+}
@@ -0,0 +1,267 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+
+use serde::Serialize;
+use tetroku_lib::{GeneratorKind, PlacementOutcome, ScoringKind, StrategyKind, WouldRemove};
+
+/// What happened in a single game.
+#[derive(Debug, Default, Clone)]
+pub struct GameStats {
+	pub score: u32,
+	pub placements: usize,
+	pub clears: Clears,
+	/// The names of the minos left in the batch when the game was lost, without duplicates,
+	/// or `None` if the game reached the placement limit.
+	pub stuck: Option<Vec<String>>,
+	/// With `--survivable`, the number of batches rerolled.
+	pub rerolls: u64,
+	/// With `--survivable`, the number of batches dealt without being survivable.
+	pub fallbacks: u64,
+}
+
+impl GameStats {
+	pub fn record(&mut self, outcome: &PlacementOutcome) {
+		self.placements += 1;
+		self.score += outcome.points;
+		for cleared in &outcome.cleared {
+			match cleared {
+				WouldRemove::Horizontal { .. } => self.clears.rows += 1,
+				WouldRemove::Vertical { .. } => self.clears.columns += 1,
+				WouldRemove::Square { .. } => self.clears.squares += 1,
+			}
+		}
+	}
+}
+
+#[derive(Debug, Default, Clone, Copy, Serialize)]
+pub struct Clears {
+	pub rows: u64,
+	pub columns: u64,
+	pub squares: u64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Distribution {
+	pub mean: f64,
+	pub median: f64,
+	pub max: u64,
+}
+
+impl Distribution {
+	/// Returns zeros if `values` is empty.
+	#[allow(clippy::cast_precision_loss /* approximate statistics */)]
+	fn of(mut values: Vec<u64>) -> Self {
+		values.sort_unstable();
+		let len = values.len();
+		if len == 0 {
+			return Self {
+				mean: 0.0,
+				median: 0.0,
+				max: 0,
+			};
+		}
+		Self {
+			mean: values.iter().sum::<u64>() as f64 / len as f64,
+			// The two middle values, which are the same one if `len` is odd.
+			median: (values[(len - 1) / 2] + values[len / 2]) as f64 / 2.0,
+			max: values[len - 1],
+		}
+	}
+}
+
+/// Aggregate statistics over all simulated games, along with the settings they were played with.
+#[derive(Debug, Clone, Serialize)]
+pub struct Summary {
+	pub games: u64,
+	pub first_seed: u64,
+	pub strategy: StrategyKind,
+	pub generator: GeneratorKind,
+	pub survivable: bool,
+	pub scoring: ScoringKind,
+	pub score: Distribution,
+	/// The number of placements per game.
+	pub length: Distribution,
+	/// The total number of regions cleared, by type.
+	pub clears: Clears,
+	pub lost: u64,
+	/// The number of games that were stopped at the placement limit.
+	pub unfinished: u64,
+	/// For each mino, the number of lost games in which it was left in the batch.
+	pub stuck_minos: BTreeMap<String, u64>,
+	/// The number of batches rerolled per game. Always zero without `--survivable`.
+	pub rerolls: Distribution,
+	/// The total number of batches dealt without being survivable, because no survivable batch was found in time.
+	pub fallbacks: u64,
+}
+
+impl Summary {
+	pub fn new(
+		games: &[GameStats],
+		first_seed: u64,
+		strategy: StrategyKind,
+		rules: &tetroku_lib::Rules,
+	) -> Self {
+		let mut clears = Clears::default();
+		let mut stuck_minos = BTreeMap::new();
+		let mut lost = 0;
+		let mut fallbacks = 0;
+		for game in games {
+			fallbacks += game.fallbacks;
+			clears.rows += game.clears.rows;
+			clears.columns += game.clears.columns;
+			clears.squares += game.clears.squares;
+			if let Some(stuck) = &game.stuck {
+				lost += 1;
+				for name in stuck {
+					*stuck_minos.entry(name.clone()).or_default() += 1;
+				}
+			}
+		}
+		let num_games = u64::try_from(games.len()).unwrap_or_else(|_| unreachable!());
+		Self {
+			games: num_games,
+			first_seed,
+			strategy,
+			generator: rules.generator,
+			survivable: rules.survivable,
+			scoring: rules.scoring,
+			score: Distribution::of(games.iter().map(|game| u64::from(game.score)).collect()),
+			length: Distribution::of(
+				games
+					.iter()
+					.map(|game| u64::try_from(game.placements).unwrap_or_else(|_| unreachable!()))
+					.collect(),
+			),
+			clears,
+			lost,
+			unfinished: num_games - lost,
+			stuck_minos,
+			rerolls: Distribution::of(games.iter().map(|game| game.rerolls).collect()),
+			fallbacks,
+		}
+	}
+}
+
+impl Display for Summary {
+	#[allow(clippy::cast_precision_loss /* approximate statistics */)]
+	fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		let per_game = |total: u64| total as f64 / self.games.max(1) as f64;
+		writeln!(
+			formatter,
+			"{} games with the {} strategy, from seed {}",
+			self.games, self.strategy, self.first_seed
+		)?;
+		writeln!(
+			formatter,
+			"Score: mean {:.1}, median {}, max {}",
+			self.score.mean, self.score.median, self.score.max
+		)?;
+		writeln!(
+			formatter,
+			"Length: mean {:.1}, median {}, max {} placements",
+			self.length.mean, self.length.median, self.length.max
+		)?;
+		writeln!(
+			formatter,
+			"Clears per game: {:.2} rows, {:.2} columns, {:.2} squares",
+			per_game(self.clears.rows),
+			per_game(self.clears.columns),
+			per_game(self.clears.squares)
+		)?;
+		writeln!(
+			formatter,
+			"Lost: {}, stopped at the placement limit: {}",
+			self.lost, self.unfinished
+		)?;
+
+		if self.survivable {
+			writeln!(
+				formatter,
+				"Rerolls per game: mean {:.1}, median {}, max {}; unsurvivable batches dealt: {}",
+				self.rerolls.mean, self.rerolls.median, self.rerolls.max, self.fallbacks
+			)?;
+		}
+
+		let mut stuck: Vec<(&String, &u64)> = self.stuck_minos.iter().collect();
+		stuck.sort_by_key(|&(_, &count)| std::cmp::Reverse(count));
+		if !stuck.is_empty() {
+			writeln!(formatter, "Minos left in the batch when losing:")?;
+		}
+		for (name, count) in stuck {
+			writeln!(formatter, "  {name}: {count} games")?;
+		}
+		Ok(())
+	}
+}
+
+#[test]
+#[allow(clippy::float_cmp /* the values are exact */)]
+fn test_distribution() {
+	let odd = Distribution::of(vec![7, 1, 4]);
+	assert_eq!(odd.mean, 4.0);
+	assert_eq!(odd.median, 4.0);
+	assert_eq!(odd.max, 7);
+
+	let even = Distribution::of(vec![10, 1, 3, 2]);
+	assert_eq!(even.mean, 4.0);
+	assert_eq!(even.median, 2.5);
+	assert_eq!(even.max, 10);
+
+	let empty = Distribution::of(Vec::new());
+	assert_eq!(empty.mean, 0.0);
+	assert_eq!(empty.median, 0.0);
+	assert_eq!(empty.max, 0);
+}
+
+#[test]
+#[allow(clippy::float_cmp /* the values are exact */)]
+fn test_summary() {
+	let game = |score, placements, stuck: bool| GameStats {
+		score,
+		placements,
+		stuck: stuck.then(|| vec!["t".to_owned()]),
+		..GameStats::default()
+	};
+	let rules = tetroku_lib::Rules::default();
+
+	let odd = Summary::new(
+		&[game(30, 5, true), game(10, 2, true), game(50, 20, false)],
+		4,
+		StrategyKind::Greedy,
+		&rules,
+	);
+	assert_eq!(odd.games, 3);
+	assert_eq!(odd.first_seed, 4);
+	assert_eq!(
+		(odd.score.mean, odd.score.median, odd.score.max),
+		(30.0, 30.0, 50)
+	);
+	assert_eq!(
+		(odd.length.mean, odd.length.median, odd.length.max),
+		(9.0, 5.0, 20)
+	);
+	assert_eq!((odd.lost, odd.unfinished), (2, 1));
+	assert_eq!(odd.stuck_minos.get("t"), Some(&2));
+
+	let even = Summary::new(
+		&[
+			game(30, 5, true),
+			game(10, 2, true),
+			game(50, 20, true),
+			game(0, 1, true),
+		],
+		0,
+		StrategyKind::Greedy,
+		&rules,
+	);
+	assert_eq!(even.games, 4);
+	assert_eq!(
+		(even.score.mean, even.score.median, even.score.max),
+		(22.5, 20.0, 50)
+	);
+	assert_eq!(
+		(even.length.mean, even.length.median, even.length.max),
+		(7.0, 3.5, 20)
+	);
+	assert_eq!((even.lost, even.unfinished), (4, 0));
+}
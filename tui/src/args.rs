@@ -0,0 +1,102 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context as _};
+use tetroku_lib::{RuleArgs, Rules, StrategyKind};
+
+const USAGE: &str = "\
+Usage: tetroku-tui [OPTIONS]
+       tetroku-tui verify <FILE>
+
+Commands:
+  verify <FILE>             Play back the replay in FILE, check every move, batch and the claimed score,
+                            and print the score, whether the game is over, the undos, hints and bot moves used,
+                            and whether the rules are standard.
+
+Options:
+  --seed <SEED>             Seed for mino generation. Random if not specified.
+  --undo-limit <COUNT>      Only allow undoing the last COUNT placements. 0 disables undo. Unlimited by default.
+  --strategy <STRATEGY>     The bot used for hints and automatic moves: `random`, `greedy`, or `heuristic` (default).
+  --record <FILE>           Save a replay of the game to FILE when it's lost, restarted or quit.
+                            Games after a restart go to FILE with `-2`, `-3`, ... before the extension.
+  --replay <FILE>           Watch the game recorded in FILE instead of playing. Conflicts with `--record`.
+  -h, --help                Print this help.
+
+Rule options:";
+
+#[derive(Debug, Clone, Default)]
+pub struct Args {
+	pub seed: Option<u64>,
+	pub rules: Rules,
+	pub strategy: StrategyKind,
+	pub record: Option<String>,
+	pub replay: Option<String>,
+	/// Set by the `verify` command, which takes no options.
+	pub verify: Option<String>,
+}
+
+fn parse_value<T: FromStr>(name: &str, value: &str) -> anyhow::Result<T>
+where
+	T::Err: std::error::Error + Send + Sync + 'static,
+{
+	value
+		.parse()
+		.with_context(|| format!("invalid value {value:?} for {name}"))
+}
+
+impl Args {
+	/// Parse the process's command-line arguments, printing the usage and exiting if `--help` is passed.
+	pub fn parse() -> anyhow::Result<Self> {
+		let mut ret = Self::default();
+
+		let mut rule_args = RuleArgs::new();
+
+		let mut args = std::env::args().skip(1).peekable();
+		if args.next_if_eq("verify").is_some() {
+			let path = args
+				.next()
+				.ok_or_else(|| anyhow!("missing replay file for verify"))?;
+			if let Some(extra) = args.next() {
+				bail!(
+					"unexpected argument {extra:?} for verify\n\n{USAGE}\n{}",
+					RuleArgs::USAGE
+				);
+			}
+			ret.verify = Some(path);
+			return Ok(ret);
+		}
+		while let Some(arg) = args.next() {
+			if rule_args.parse(&arg, &mut args)? {
+				continue;
+			}
+			let mut value = || {
+				args
+					.next()
+					.ok_or_else(|| anyhow!("missing value for {arg}"))
+			};
+
+			match arg.as_str() {
+				"--seed" => ret.seed = Some(parse_value(&arg, &value()?)?),
+				"--undo-limit" => ret.rules.undo_limit = Some(parse_value(&arg, &value()?)?),
+				"--strategy" => ret.strategy = parse_value(&arg, &value()?)?,
+				"--record" => ret.record = Some(value()?),
+				"--replay" => ret.replay = Some(value()?),
+				"-h" | "--help" => {
+					println!("{USAGE}\n{}", RuleArgs::USAGE);
+					std::process::exit(0);
+				}
+				_ => bail!("unknown argument {arg:?}\n\n{USAGE}\n{}", RuleArgs::USAGE),
+			}
+		}
+
+		if ret.record.is_some() && ret.replay.is_some() {
+			bail!("--replay conflicts with --record");
+		}
+
+		ret.rules = Rules {
+			undo_limit: ret.rules.undo_limit,
+			..rule_args.into_rules()?
+		};
+
+		Ok(ret)
+	}
+}
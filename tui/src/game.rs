@@ -1,197 +1,170 @@
-use tetroku_lib::{Board, Coordinate, Mino, Position};
+use tetroku_lib::{
+	AnyGenerator, AnyStrategy, Board, Coordinate, Layout, Mino, Position, Replay, ReplayMove, Rules,
+	ScoringKind, Strategy as _, StrategyKind,
+};
 
 use crate::util::{cursive_to_tuple, XY};
 
-#[derive(Debug, Clone, Copy)]
-struct Placing {
-	idx: usize,
-	/// X and Y here are the position within the board of the top-left corner of the mino's 5x5 container.
-	pos: XY,
-}
-
-pub const TILE_BATCH_SIZE: usize = 3;
-
 #[derive(Debug, Clone, Copy)]
 struct Dragging {
 	/// `mouse_position.map_x(|x| x / 2) + relative_mino_pos = mino_pos`
 	relative_mino_pos: XY<isize>,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct Game {
-	board: Board,
-	minos: [Option<Mino>; TILE_BATCH_SIZE],
-	/// Invariant: when `placing` is `Some`, `minos[placing.idx]` is `Some`.
-	placing: Option<Placing>,
-
-	score: u32,
-	last_points: u32,
-	lost: bool,
+	inner: tetroku_lib::Game<AnyGenerator, ScoringKind>,
+	/// Every move made so far, for `--record`.
+	replay: Replay,
+	/// Whether this is a step of a replay being watched, which can't be played.
+	viewing: bool,
+	/// The bot that suggests placements and makes them on request.
+	strategy: AnyStrategy,
 
 	dragging: Option<Dragging>,
 }
 
-fn random_mino() -> Mino {
-	let rng = &mut rand::thread_rng();
-
-	let mut mino = rand::seq::IteratorRandom::choose(Mino::all(), rng).unwrap();
-
-	// Perform random transformations.
-	if rand::random() {
-		mino = mino.flip_horizontal();
-	}
-	if rand::random() {
-		mino = mino.flip_vertical();
-	}
-	mino = match (rand::random(), rand::random()) {
-		(false, false) => mino,
-		(false, true) => mino.rotate_cw_90(),
-		(true, false) => mino.rotate_180(),
-		(true, true) => mino.rotate_ccw_90(),
-	};
-
-	mino
-}
-
 impl Game {
-	pub fn new() -> Self {
-		let mut ret = Self {
-			board: Board::new(),
-			minos: [None; 3],
-			placing: None,
-
-			score: 0,
-			last_points: 0,
-			lost: false,
+	pub fn new(rules: &Rules, seed: u64, strategy: StrategyKind) -> Self {
+		let inner = rules.new_game(seed);
+		let mut replay = Replay::new(rules.clone(), seed);
+		replay.moves.extend(ReplayMove::deal(&inner));
+		Self {
+			inner,
+			replay,
+			viewing: false,
+			// Flip the bits so that the strategy doesn't draw the same numbers as the generator.
+			strategy: AnyStrategy::new(strategy, !seed),
 
 			dragging: None,
-		};
-
-		ret.generate_minos();
-		ret.start_placing_next();
-
-		ret
+		}
 	}
 
-	fn generate_minos(&mut self) {
-		self.minos.fill_with(|| Some(random_mino()));
-		self.update_lost();
-	}
+	/// `replay` after its first `step` moves, where `inner` is the game at that point, as returned by `Replay::play_back`.
+	/// If the next move is a placement, it is shown as the selected mino.
+	pub fn viewing(
+		replay: &Replay,
+		step: usize,
+		mut inner: tetroku_lib::Game<AnyGenerator, ScoringKind>,
+	) -> Self {
+		if let Some(&ReplayMove::Place {
+			index, position, ..
+		}) = replay.moves.get(step)
+		{
+			inner.select(index);
+			inner.set_selected_position(position);
+		}
+		Self {
+			inner,
+			replay: Replay {
+				moves: replay.moves[..step].to_vec(),
+				..replay.clone()
+			},
+			viewing: true,
+			// Never used, since hints are disabled while viewing.
+			strategy: AnyStrategy::new(StrategyKind::default(), replay.seed),
 
-	fn update_lost(&mut self) {
-		self.lost = self
-			.minos
-			.iter()
-			.flatten()
-			.all(|&mino| !self.board.can_place_anywhere(mino));
+			dragging: None,
+		}
 	}
 
 	pub fn start_placing(&mut self, idx: usize) {
-		let Some(mino) = &self.minos[idx] else {
+		if self.viewing {
 			return;
-		};
-		let min_point: XY = mino.min_point().into();
-		self.placing = Some(Placing {
-			idx,
-			pos: min_point.map(|v| -v),
-		});
-	}
-
-	fn start_placing_next(&mut self) {
-		if let Some(next_to_place) = self
-			.minos
-			.iter()
-			.position(|mino| mino.map_or(false, |mino| self.board.can_place_anywhere(mino)))
-		{
-			self.start_placing(next_to_place);
 		}
+		self.inner.select(idx);
 	}
 
 	pub fn finish_placing(&mut self) {
-		let Some(placing) = self.placing else {
+		let Some(selected) = self.inner.selected().filter(|_| !self.viewing) else {
 			return;
 		};
-		let mino = self.minos[placing.idx].unwrap();
-		match self.board.place_at(mino, cursive_to_tuple(placing.pos)) {
-			Ok(()) => (),
-			Err(..) => return,
+		if let Ok(outcome) = self.inner.play(selected.index, selected.position) {
+			self.replay.moves.push(ReplayMove::Place {
+				index: selected.index,
+				mino: outcome.mino,
+				position: outcome.position,
+			});
+			self.replay.moves.extend(ReplayMove::deal(&self.inner));
 		}
+	}
 
-		let mut points = 0;
-
-		points += u32::try_from(mino.num_squares()).unwrap();
-		self.minos[placing.idx] = None;
-		self.placing = None;
+	/// Select the mino the bot would place next, at the position it would place it.
+	/// Hints are recorded, so that verifying the replay reveals them.
+	pub fn hint(&mut self) {
+		if self.viewing {
+			return;
+		}
+		if let Some(selection) = self.strategy.choose(self.inner.turn()) {
+			self.replay.moves.push(ReplayMove::Hint);
+			self.dragging = None;
+			self.inner.select(selection.index);
+			self.inner.set_selected_position(selection.position);
+		}
+	}
 
-		let feature_count = u32::try_from(self.board.remove_filled()).unwrap();
-		points += 3 * feature_count + 3 * feature_count.saturating_sub(1);
+	/// Let the bot make the next placement.
+	pub fn bot_move(&mut self) {
+		self.hint();
+		self.finish_placing();
+	}
 
-		self.last_points = points;
-		self.score += points;
+	pub fn undo(&mut self) {
+		self.dragging = None;
+		if self.inner.undo() {
+			self.replay.moves.push(ReplayMove::Undo);
+		}
+	}
 
-		self.update_lost();
-		if self.minos.iter().all(Option::is_none) {
-			self.generate_minos();
+	pub fn redo(&mut self) {
+		self.dragging = None;
+		if self.inner.redo() {
+			self.replay.moves.push(ReplayMove::Redo);
 		}
+	}
 
-		self.start_placing_next();
+	/// The moves made so far, along with the current score.
+	pub fn replay(&self) -> Replay {
+		Replay {
+			score: Some(self.score()),
+			..self.replay.clone()
+		}
 	}
 
 	pub fn move_placing(&mut self, dx: Coordinate, dy: Coordinate) {
-		let Some(placing) = &mut self.placing else {
-			return;
-		};
-		let mino = self.minos[placing.idx].unwrap();
-
-		let new_pos = placing.pos + XY { x: dx, y: dy };
-
-		if Board::is_in_bounds(mino, cursive_to_tuple(new_pos)) {
-			placing.pos = new_pos;
-		} else {
-			let mut new_pos = placing.pos;
-			loop {
-				let check = new_pos - XY { x: dx, y: dy };
-				if Board::is_in_bounds(mino, cursive_to_tuple(check)) {
-					new_pos = check;
-				} else {
-					break;
-				}
-			}
-			placing.pos = new_pos;
-		}
+		self.inner.move_selected(dx, dy);
 	}
 
 	pub fn placing(&self) -> Option<(Mino, XY)> {
 		self
-			.placing
-			.map(|placing| (self.minos[placing.idx].unwrap(), placing.pos))
+			.inner
+			.selected_mino()
+			.map(|(mino, position)| (mino, position.into()))
 	}
 
 	pub fn start_dragging(&mut self, mouse_position: XY<isize>) {
-		let Some(placing) = self.placing else {
+		let Some((_, placing_pos)) = self.placing().filter(|_| !self.viewing) else {
 			return;
 		};
 
 		// mouse.map + rel = mino
 		// rel = mino - mouse.map
 		self.dragging = Some(Dragging {
-			relative_mino_pos: placing.pos.map(isize::from) - mouse_position.map_x(|x| x / 2),
+			relative_mino_pos: placing_pos.map(isize::from) - mouse_position.map_x(|x| x / 2),
 		});
 	}
 
 	pub fn continue_dragging(&mut self, mouse_position: XY<isize>) {
-		let Some(placing) = &mut self.placing else {
-			return;
-		};
 		let Some(dragging) = self.dragging else {
 			return;
 		};
-		let mino = self.minos[placing.idx].unwrap();
 
 		// mouse / 2 + rel = mino
 		let new_mino_pos = mouse_position.map_x(|x| x / 2) + dragging.relative_mino_pos;
 		let new_mino_pos = new_mino_pos.map(|v| v.try_into().unwrap());
-		placing.pos = Board::clamp_mino_position(mino, cursive_to_tuple(new_mino_pos)).into();
+		self
+			.inner
+			.set_selected_position(cursive_to_tuple(new_mino_pos));
 	}
 
 	pub fn finish_dragging(&mut self) {
@@ -199,15 +172,16 @@ impl Game {
 	}
 
 	pub fn would_remove_board(&self) -> Board {
-		let mut ret = Board::new();
+		let mut ret = Board::with_layout(self.layout());
 
 		if let Some((mino, mino_position)) = self.placing() {
 			if let Ok(would_remove) = self
-				.board
+				.inner
+				.board()
 				.find_would_remove(mino, cursive_to_tuple(mino_position))
 			{
 				for would_remove in would_remove {
-					for position in would_remove.iter() {
+					for position in would_remove.iter(self.layout()) {
 						ret.set(position, true);
 					}
 				}
@@ -217,16 +191,39 @@ impl Game {
 		ret
 	}
 
+	pub fn layout(&self) -> &'static Layout {
+		self.inner.board().layout()
+	}
+
 	pub fn occupied(&self, position: Position) -> bool {
-		self.board.occupied(position)
+		self.inner.board().occupied(position)
+	}
+
+	pub fn seed(&self) -> u64 {
+		self.replay.seed
+	}
+
+	/// With `--survivable`, the number of batches rerolled before the current batch was dealt,
+	/// and whether it was dealt anyway because none of the rerolls were survivable.
+	pub fn rerolls(&self) -> Option<(u32, bool)> {
+		let generator = self.inner.generator().as_survivable()?;
+		Some((generator.last_rerolls(), generator.last_fell_back()))
+	}
+
+	pub fn is_viewing(&self) -> bool {
+		self.viewing
 	}
 
 	pub fn lost(&self) -> bool {
-		self.lost
+		self.inner.lost()
 	}
 
 	pub fn score(&self) -> u32 {
-		self.score
+		self.inner.score()
+	}
+
+	pub fn streak(&self) -> u32 {
+		self.inner.streak()
 	}
 }
 
@@ -241,11 +238,12 @@ pub struct MinoState {
 
 impl Game {
 	pub fn mino_state(&self, mino_idx: usize) -> Option<MinoState> {
-		let mino = self.minos[mino_idx]?;
-		let can_place = self.board.can_place_anywhere(mino);
+		let mino = self.inner.mino(mino_idx)?;
+		let can_place = self.inner.can_place(mino_idx);
 		let is_placing = self
-			.placing
-			.map_or(false, |placing| placing.idx == mino_idx);
+			.inner
+			.selected()
+			.is_some_and(|selected| selected.index == mino_idx);
 		Some(MinoState {
 			mino,
 			can_place,
@@ -8,8 +8,6 @@
 	non_ascii_idents,
 	nonstandard_style,
 	noop_method_call,
-	pointer_structural_match,
-	private_in_public,
 	rust_2018_idioms,
 	unused_qualifications
 )]
@@ -20,53 +18,79 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use cursive::event::Key;
-use cursive::views::{DummyView, LinearLayout, Panel};
+use anyhow::Context as _;
+use cursive::event::{Event, Key};
+use cursive::traits::{Nameable as _, Resizable as _};
+use cursive::views::{Dialog, DummyView, EditView, LinearLayout, OnEventView, Panel, TextView};
 use cursive::{Cursive, CursiveExt};
+use tetroku_lib::{Replay, BATCH_SIZE};
 
-use crate::game::{Game, TILE_BATCH_SIZE};
+use crate::args::Args;
+use crate::game::Game;
+use crate::recorder::Recorder;
 use crate::theme::theme;
+use crate::viewer::Viewer;
 use crate::views::board::BoardView;
 use crate::views::mino::MinoView;
 use crate::views::score::ScoreView;
+use crate::views::SharedGame;
 
+mod args;
 mod game;
+mod recorder;
 mod theme;
 mod util;
+mod viewer;
 mod views;
 
-fn main() {
-	let mut app = Cursive::new();
-	app.set_theme(theme());
-
-	let game = Rc::new(RefCell::new(Game::new()));
-
-	let score = ScoreView::new(Rc::clone(&game));
-	let board = BoardView::new(Rc::clone(&game));
+/// The score, board and batch of `game`.
+fn game_layout(game: &SharedGame) -> LinearLayout {
+	let score = ScoreView::new(Rc::clone(game));
+	let board = BoardView::new(Rc::clone(game));
 	let mino_list = {
 		let mut mino_list = LinearLayout::horizontal();
 		mino_list.add_child(DummyView);
-		for idx in 0..TILE_BATCH_SIZE {
-			mino_list.add_child(MinoView::new(Rc::clone(&game), idx));
+		for idx in 0..BATCH_SIZE {
+			mino_list.add_child(MinoView::new(Rc::clone(game), idx));
 			mino_list.add_child(DummyView);
 		}
 		mino_list
 	};
-	let layout = LinearLayout::vertical()
+	LinearLayout::vertical()
 		.child(score)
 		.child(DummyView)
 		.child(board)
 		.child(DummyView)
-		.child(mino_list);
+		.child(mino_list)
+}
 
-	let root = Panel::new(layout).title("Tetroku");
+/// Show `result`'s error, if any, in a dialog.
+fn report(app: &mut Cursive, result: anyhow::Result<()>) {
+	if let Err(error) = result {
+		app.add_layer(Dialog::info(format!("{error:#}")));
+	}
+}
 
-	app.add_layer(root);
+fn play(app: &mut Cursive, args: &Args) -> anyhow::Result<()> {
+	let new_game = {
+		let args = args.clone();
+		// Restarting with a fixed seed replays the same game.
+		move || {
+			Game::new(
+				&args.rules,
+				// Random seeds are kept short so that they're easy to share.
+				args.seed.unwrap_or_else(|| rand::random::<u32>().into()),
+				args.strategy,
+			)
+		}
+	};
+
+	let game = Rc::new(RefCell::new(new_game()));
+	let recorder = Rc::new(RefCell::new(Recorder::new(args.record.as_deref())));
 
-	app.add_global_callback('q', cursive::Cursive::quit);
-	app.add_global_callback(Key::Esc, cursive::Cursive::quit);
+	app.add_layer(Panel::new(game_layout(&game)).title("Tetroku"));
 
-	for idx in 0..TILE_BATCH_SIZE {
+	for idx in 0..BATCH_SIZE {
 		let game = Rc::clone(&game);
 		let number_key = char::from_digit(u32::try_from(idx + 1).unwrap(), 10).unwrap();
 		app.add_global_callback(number_key, move |_app| {
@@ -74,12 +98,20 @@ fn main() {
 		});
 	}
 
-	app.add_global_callback(Key::Enter, {
+	// Save lost games right away, in case the player restarts or quits without undoing.
+	let bind_placement = |app: &mut Cursive, event: Event, action: fn(&mut Game)| {
 		let game = Rc::clone(&game);
-		move |_app| {
-			game.borrow_mut().finish_placing();
-		}
-	});
+		let recorder = Rc::clone(&recorder);
+		app.add_global_callback(event, move |app| {
+			let mut game = game.borrow_mut();
+			action(&mut game);
+			if game.lost() {
+				report(app, recorder.borrow().save(&game));
+			}
+		});
+	};
+	bind_placement(app, Key::Enter.into(), Game::finish_placing);
+	bind_placement(app, 'b'.into(), Game::bot_move);
 
 	for (key, dx, dy) in [
 		(Key::Right, 1, 0),
@@ -93,7 +125,159 @@ fn main() {
 		});
 	}
 
-	app.add_global_callback('r', move |_app| *game.borrow_mut() = Game::new());
+	app.add_global_callback('h', {
+		let game = Rc::clone(&game);
+		move |_app| game.borrow_mut().hint()
+	});
+
+	app.add_global_callback('u', {
+		let game = Rc::clone(&game);
+		move |_app| game.borrow_mut().undo()
+	});
+	app.add_global_callback(Event::CtrlChar('r'), {
+		let game = Rc::clone(&game);
+		move |_app| game.borrow_mut().redo()
+	});
+
+	app.add_global_callback('r', {
+		let game = Rc::clone(&game);
+		let recorder = Rc::clone(&recorder);
+		move |app| {
+			let mut game = game.borrow_mut();
+			report(app, recorder.borrow_mut().finish_game(&game));
+			*game = new_game();
+		}
+	});
+
+	app.run();
+
+	let result = recorder.borrow().save(&game.borrow());
+	result
+}
+
+/// Show the current step of `viewer` in `game` and the status line.
+fn show_step(app: &mut Cursive, viewer: &Viewer, game: &SharedGame) {
+	*game.borrow_mut() = viewer.game();
+	app.call_on_name("status", |status: &mut TextView| {
+		status.set_content(viewer.status().join("\n"));
+	});
+}
+
+fn view_replay(app: &mut Cursive, path: &str) -> anyhow::Result<()> {
+	let viewer = Rc::new(RefCell::new(
+		Viewer::new(read_replay(path)?).with_context(|| format!("{path:?} can't be played back"))?,
+	));
+	let game = Rc::new(RefCell::new(viewer.borrow().game()));
+
+	let layout = game_layout(&game).child(DummyView).child(
+		TextView::new(viewer.borrow().status().join("\n"))
+			.center()
+			.with_name("status"),
+	);
+	app.add_layer(Panel::new(layout).title("Tetroku replay"));
+
+	let bind = |app: &mut Cursive, event: Event, action: fn(&mut Viewer)| {
+		let viewer = Rc::clone(&viewer);
+		let game = Rc::clone(&game);
+		app.add_global_callback(event, move |app| {
+			action(&mut viewer.borrow_mut());
+			show_step(app, &viewer.borrow(), &game);
+		});
+	};
+	bind(app, Key::Right.into(), Viewer::forward);
+	bind(app, Key::Left.into(), Viewer::back);
+	bind(app, Key::Home.into(), |viewer| viewer.jump(0));
+	bind(app, Key::End.into(), |viewer| viewer.jump(usize::MAX));
+	bind(app, ' '.into(), Viewer::toggle_autoplay);
+	bind(app, '+'.into(), Viewer::faster);
+	bind(app, '-'.into(), Viewer::slower);
+
+	app.add_global_callback('g', {
+		let viewer = Rc::clone(&viewer);
+		let game = Rc::clone(&game);
+		move |app| {
+			let viewer = Rc::clone(&viewer);
+			let game = Rc::clone(&game);
+			let input = EditView::new().on_submit(move |app, text| {
+				app.pop_layer();
+				if let Ok(step) = text.trim().parse() {
+					viewer.borrow_mut().jump(step);
+					show_step(app, &viewer.borrow(), &game);
+				}
+			});
+			let dialog = Dialog::around(input.fixed_width(8)).title("Jump to move");
+			app.add_layer(OnEventView::new(dialog).on_event(Key::Esc, |app| {
+				app.pop_layer();
+			}));
+		}
+	});
+
+	app.set_fps(30);
+	app.add_global_callback(Event::Refresh, move |app| {
+		if viewer.borrow_mut().tick() {
+			show_step(app, &viewer.borrow(), &game);
+		}
+	});
 
 	app.run();
+
+	Ok(())
+}
+
+fn read_replay(path: &str) -> anyhow::Result<Replay> {
+	std::fs::read_to_string(path)
+		.with_context(|| format!("could not read {path:?}"))?
+		.parse()
+		.with_context(|| format!("invalid replay in {path:?}"))
+}
+
+fn verify(path: &str) -> anyhow::Result<()> {
+	let replay = read_replay(path)?;
+	let verification = replay
+		.verify()
+		.with_context(|| format!("{path:?} failed verification"))?;
+	println!("Verified score: {}", verification.score);
+	println!(
+		"{}; placements: {}, undos: {}, hints and bot moves: {}",
+		if verification.lost {
+			"Game over"
+		} else {
+			"Unfinished"
+		},
+		verification.placements,
+		verification.undos,
+		verification.hints
+	);
+	println!(
+		"Rules: {}, {}",
+		if replay.rules.is_standard() {
+			"standard"
+		} else {
+			"non-standard (see the file)"
+		},
+		match replay.rules.undo_limit {
+			None => "unlimited undo".to_owned(),
+			Some(0) => "no undo".to_owned(),
+			Some(limit) => format!("undo limited to {limit}"),
+		}
+	);
+	Ok(())
+}
+
+fn main() -> anyhow::Result<()> {
+	let args = Args::parse()?;
+	if let Some(path) = &args.verify {
+		return verify(path);
+	}
+
+	let mut app = Cursive::new();
+	app.set_theme(theme());
+
+	app.add_global_callback('q', Cursive::quit);
+	app.add_global_callback(Key::Esc, Cursive::quit);
+
+	match &args.replay {
+		Some(path) => view_replay(&mut app, path),
+		None => play(&mut app, &args),
+	}
 }
@@ -0,0 +1,89 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context as _;
+use tetroku_lib::ReplayMove;
+#[cfg(test)]
+use tetroku_lib::{Rules, StrategyKind};
+
+use crate::game::Game;
+
+/// Saves replays for `--record`: the first game to the given path,
+/// and each game after restarting to the same path with `-2`, `-3` and so on before the extension.
+pub struct Recorder {
+	path: Option<PathBuf>,
+	/// The number of the current game, starting from 1.
+	number: usize,
+}
+
+impl Recorder {
+	pub fn new(path: Option<&str>) -> Self {
+		Self {
+			path: path.map(PathBuf::from),
+			number: 1,
+		}
+	}
+
+	fn numbered_path(path: &Path, number: usize) -> PathBuf {
+		if number == 1 {
+			return path.to_owned();
+		}
+		let mut name = path.file_stem().unwrap_or_default().to_owned();
+		name.push(format!("-{number}"));
+		if let Some(extension) = path.extension() {
+			name.push(".");
+			name.push(extension);
+		}
+		path.with_file_name(name)
+	}
+
+	/// Write the replay of `game`, replacing any earlier save of the same game.
+	pub fn save(&self, game: &Game) -> anyhow::Result<()> {
+		let Some(path) = &self.path else {
+			return Ok(());
+		};
+		let path = Self::numbered_path(path, self.number);
+		std::fs::write(&path, game.replay().to_string())
+			.with_context(|| format!("could not write the replay to {}", path.display()))
+	}
+
+	/// Save `game` before it's replaced by a new one. Games without any placements aren't saved.
+	pub fn finish_game(&mut self, game: &Game) -> anyhow::Result<()> {
+		let placed = game
+			.replay()
+			.moves
+			.iter()
+			.any(|game_move| matches!(game_move, ReplayMove::Place { .. }));
+		if !placed {
+			return Ok(());
+		}
+		let result = self.save(game);
+		self.number += 1;
+		result
+	}
+}
+
+#[test]
+fn test_finish_game() {
+	let dir = std::env::temp_dir().join(format!("tetroku-recorder-test-{}", std::process::id()));
+	std::fs::create_dir_all(&dir).unwrap();
+	let path = dir.join("game.txt");
+	let mut recorder = Recorder::new(path.to_str());
+
+	// Restarting an untouched game doesn't save it or use up a number.
+	let mut game = Game::new(&Rules::default(), 1, StrategyKind::default());
+	recorder.finish_game(&game).unwrap();
+	assert!(!path.exists());
+
+	game.start_placing(0);
+	game.finish_placing();
+	recorder.finish_game(&game).unwrap();
+	assert!(path.exists());
+	recorder
+		.finish_game(&Game::new(&Rules::default(), 2, StrategyKind::default()))
+		.unwrap();
+	assert!(!dir.join("game-2.txt").exists());
+	recorder.save(&game).unwrap();
+	assert!(dir.join("game-2.txt").exists());
+
+	std::fs::remove_dir_all(dir).unwrap();
+}
@@ -8,6 +8,7 @@ pub struct GameColors {
 	pub would_conflict: Color,
 	pub would_be_removed: Color,
 	pub would_be_filled_and_removed: Color,
+	pub border: Color,
 }
 
 pub const GAME_COLORS: GameColors = GameColors {
@@ -19,6 +20,7 @@ pub const GAME_COLORS: GameColors = GameColors {
 	would_conflict: Color::Light(BaseColor::Red),
 	would_be_removed: Color::Light(BaseColor::Yellow),
 	would_be_filled_and_removed: Color::Light(BaseColor::Magenta),
+	border: Color::Dark(BaseColor::Black),
 };
 
 pub fn theme() -> Theme {
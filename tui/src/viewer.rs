@@ -0,0 +1,105 @@
+use std::time::{Duration, Instant};
+
+use tetroku_lib::{AnyGenerator, Replay, ReplayError, ReplayMove, ScoringKind};
+
+use crate::game::Game;
+
+/// The autoplay speeds, in moves per second.
+const SPEEDS: [f64; 7] = [0.5, 1.0, 2.0, 4.0, 8.0, 16.0, 30.0];
+const DEFAULT_SPEED: usize = 2;
+
+/// Steps through a replay, either by hand or automatically.
+pub struct Viewer {
+	replay: Replay,
+	/// The game before each move, followed by the game after the last one.
+	games: Vec<tetroku_lib::Game<AnyGenerator, ScoringKind>>,
+	/// The number of moves played so far.
+	step: usize,
+	/// An index into `SPEEDS`.
+	speed: usize,
+	playing: bool,
+	last_step: Instant,
+}
+
+impl Viewer {
+	pub fn new(replay: Replay) -> Result<Self, ReplayError> {
+		let games = replay.play_back()?;
+		Ok(Self {
+			replay,
+			games,
+			step: 0,
+			speed: DEFAULT_SPEED,
+			playing: false,
+			last_step: Instant::now(),
+		})
+	}
+
+	pub fn num_moves(&self) -> usize {
+		self.replay.moves.len()
+	}
+
+	/// The game after the moves played so far, showing the next placement.
+	pub fn game(&self) -> Game {
+		Game::viewing(&self.replay, self.step, self.games[self.step].clone())
+	}
+
+	/// Show the game after the first `step` moves, or after all of them if there aren't that many.
+	pub fn jump(&mut self, step: usize) {
+		self.step = step.min(self.num_moves());
+		self.last_step = Instant::now();
+	}
+
+	pub fn forward(&mut self) {
+		self.jump(self.step + 1);
+	}
+
+	pub fn back(&mut self) {
+		self.jump(self.step.saturating_sub(1));
+	}
+
+	pub fn toggle_autoplay(&mut self) {
+		self.playing = !self.playing;
+		if self.playing && self.step == self.num_moves() {
+			self.step = 0;
+		}
+		self.last_step = Instant::now();
+	}
+
+	pub fn faster(&mut self) {
+		self.speed = (self.speed + 1).min(SPEEDS.len() - 1);
+	}
+
+	pub fn slower(&mut self) {
+		self.speed = self.speed.saturating_sub(1);
+	}
+
+	/// Advance by a move if autoplay is on and it's time to. Returns whether the step changed.
+	pub fn tick(&mut self) -> bool {
+		let delay = Duration::from_secs_f64(1.0 / SPEEDS[self.speed]);
+		if !self.playing || self.last_step.elapsed() < delay {
+			return false;
+		}
+		self.forward();
+		if self.step == self.num_moves() {
+			self.playing = false;
+		}
+		true
+	}
+
+	pub fn status(&self) -> [String; 3] {
+		let next = match self.replay.moves.get(self.step) {
+			None => "End of the replay".into(),
+			Some(ReplayMove::Place { index, .. }) => format!("Next: mino {}", index + 1),
+			Some(ReplayMove::Undo) => "Next: undo".into(),
+			Some(ReplayMove::Redo) => "Next: redo".into(),
+			Some(ReplayMove::Deal { .. }) => "Next: new batch".into(),
+			Some(ReplayMove::Hint) => "Next: hint".into(),
+		};
+		let playing = if self.playing { "Playing" } else { "Paused" };
+		[
+			format!("Move {}/{}", self.step, self.num_moves()),
+			next,
+			format!("{playing}, {} moves/s", SPEEDS[self.speed]),
+		]
+	}
+}
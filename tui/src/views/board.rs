@@ -1,9 +1,9 @@
 use cursive::direction::Direction;
 use cursive::event::{Event, EventResult, MouseEvent};
-use cursive::theme::{Color, ColorStyle};
+use cursive::theme::{Color, ColorStyle, Effect};
 use cursive::view::{CannotFocus, Selector, ViewNotFound};
 use cursive::View;
-use tetroku_lib::Position;
+use tetroku_lib::Coordinate;
 
 use super::SharedGame;
 use crate::theme::GAME_COLORS;
@@ -21,24 +21,40 @@ impl BoardView {
 
 impl View for BoardView {
 	fn draw(&self, printer: &cursive::Printer<'_, '_>) {
-		let print_at = |position: Position, color: Color| {
+		let game = self.game.borrow();
+		let layout = game.layout();
+
+		// Region borders are drawn as vertical lines between cells and by underlining the cells above a border.
+		let region = |x: Coordinate, y: Coordinate| {
+			layout
+				.position(x, y)
+				.map(|position| layout.square_of(position))
+		};
+		let border_below = |x: Coordinate, y: Coordinate| {
+			region(x, y).is_some() && region(x, y + 1).is_some() && region(x, y) != region(x, y + 1)
+		};
+
+		let print_at = |screen_pos: cursive::Vec2, text: &str, color: Color, underline: bool| {
 			printer.with_color(
 				ColorStyle {
 					front: color.into(),
 					back: GAME_COLORS.background.into(),
 				},
 				|printer| {
-					let screen_pos = position_to_cursive(position.x() * 2, position.y());
-					printer.print(screen_pos, " \u{25a0} ");
+					if underline {
+						printer.with_effect(Effect::Underline, |printer| {
+							printer.print(screen_pos, text);
+						});
+					} else {
+						printer.print(screen_pos, text);
+					}
 				},
 			);
 		};
 
-		let game = self.game.borrow();
-
 		let would_remove_board = game.would_remove_board();
 
-		for position in Position::all() {
+		for position in layout.positions() {
 			let occupied_on_board = game.occupied(position);
 			let occupied_by_placing = game
 				.placing()
@@ -59,7 +75,31 @@ impl View for BoardView {
 				(true, true, false) => GAME_COLORS.would_conflict,
 				(true, true, true) => unreachable!(),
 			};
-			print_at(position, color);
+			let (x, y) = position.to_xy();
+			print_at(
+				position_to_cursive(x * 2 + 1, y),
+				"\u{25a0}",
+				color,
+				border_below(x, y),
+			);
+		}
+
+		// The gaps between cells, including the edges of the board.
+		for y in 0..layout.height() {
+			for x in 0..=layout.width() {
+				let (left, right) = (region(x - 1, y), region(x, y));
+				let text = if left.is_some() && right.is_some() && left != right {
+					"\u{2502}"
+				} else {
+					" "
+				};
+				print_at(
+					position_to_cursive(x * 2, y),
+					text,
+					GAME_COLORS.border,
+					border_below(x - 1, y) || border_below(x, y),
+				);
+			}
 		}
 	}
 
@@ -102,6 +142,8 @@ impl View for BoardView {
 	}
 
 	fn required_size(&mut self, _constraint: XY<usize>) -> XY<usize> {
-		XY::new(19, 9)
+		let layout = self.game.borrow().layout();
+		// Each cell is two columns wide, plus one for the trailing space of the last cell.
+		position_to_cursive(layout.width() * 2 + 1, layout.height())
 	}
 }
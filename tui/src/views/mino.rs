@@ -18,7 +18,14 @@ impl MinoView {
 		Self { game, mino_idx }
 	}
 
-	const SIZE: XY<usize> = XY { x: 5, y: 3 };
+	/// Two rows of the mino are drawn per line, in a box at least as large as that of the built-in tiles.
+	fn size(&self) -> XY<usize> {
+		let game = self.game.borrow();
+		let (width, height) = game
+			.mino_state(self.mino_idx)
+			.map_or((5, 5), |state| (state.mino.width(), state.mino.height()));
+		position_to_cursive(width.max(5), ((height + 1) / 2).max(3))
+	}
 }
 
 impl View for MinoView {
@@ -40,10 +47,11 @@ impl View for MinoView {
 				}
 			})
 		};
-		for y in (0..5).step_by(2) {
-			for x in 0..5 {
-				let top_color = color_for(mino_state.mino.at(x, y));
-				let bottom_color = color_for(mino_state.mino.at(x, y + 1));
+		let mino = mino_state.mino;
+		for y in (0..mino.height()).step_by(2) {
+			for x in 0..mino.width() {
+				let top_color = color_for(mino.at(x, y));
+				let bottom_color = color_for(mino.at(x, y + 1));
 				printer.with_color(
 					ColorStyle {
 						front: top_color.into(),
@@ -69,7 +77,7 @@ impl View for MinoView {
 		} = event
 		{
 			if let Some(relative_pos) = position.checked_sub(offset) {
-				if relative_pos.strictly_lt(Self::SIZE) {
+				if relative_pos.strictly_lt(self.size()) {
 					let mut game = self.game.borrow_mut();
 					game.start_placing(self.mino_idx);
 					game.start_dragging(relative_pos.signed());
@@ -84,11 +92,7 @@ impl View for MinoView {
 		EventResult::Ignored
 	}
 
-	fn needs_relayout(&self) -> bool {
-		false
-	}
-
 	fn required_size(&mut self, _constraint: XY<usize>) -> XY<usize> {
-		Self::SIZE
+		self.size()
 	}
 }
@@ -12,21 +12,28 @@ impl ScoreView {
 		Self { game }
 	}
 
-	fn text(&self) -> [String; 2] {
+	fn text(&self) -> [String; 3] {
 		let game = self.game.borrow();
 
-		let line1 = if game.lost() {
-			"You lost (press r)"
-		} else {
-			""
-		}
-		.into();
+		let line1 = match (game.lost(), game.is_viewing(), game.rerolls()) {
+			(true, false, _) => "You lost (press r)".into(),
+			(true, true, _) => "The game was lost".into(),
+			(false, _, Some((_, true))) => "unsurvivable batch".into(),
+			(false, _, Some((1, false))) => "1 reroll".into(),
+			(false, _, Some((rerolls, false))) if rerolls > 0 => format!("{rerolls} rerolls"),
+			(false, _, _) => String::new(),
+		};
 
 		let score = game.score();
 		let s = if score == 1 { "" } else { "s" };
-		let line2 = format!("{score} point{s}");
+		let line2 = match game.streak() {
+			0 => format!("{score} point{s}"),
+			streak => format!("{score} point{s}, streak {streak}"),
+		};
+
+		let line3 = format!("seed {}", game.seed());
 
-		[line1, line2]
+		[line1, line2, line3]
 	}
 }
 
@@ -36,17 +43,15 @@ impl View for ScoreView {
 			let left = (printer.output_size.x - text.len()) / 2;
 			printer.print((left, y), text);
 		};
-		let [line1, line2] = self.text();
+		let [line1, line2, line3] = self.text();
 		print_centered(0, &line1);
 		print_centered(1, &line2);
-	}
-
-	fn needs_relayout(&self) -> bool {
-		false
+		print_centered(2, &line3);
 	}
 
 	fn required_size(&mut self, _constraint: XY<usize>) -> XY<usize> {
 		// `self.text()` is pure ASCII so this is fine.
-		XY::new(19, 2)
+		let width = self.text().iter().map(String::len).max().unwrap_or(0);
+		XY::new(width.max(19), 3)
 	}
 }